@@ -0,0 +1,219 @@
+//! The period that's being scheduled.
+
+use chrono::{ Datelike, Local, NaiveDate };
+
+use crate::{ coverage::Coverage, shift::Shift, types::{ Day, Month } };
+
+/// The number of elements scheduled on each day, unless set otherwise
+pub const DEFAULT_HEADCOUNT: usize = 10;
+
+/// A date that isn't scheduled, even if it's on a working day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holiday {
+    pub date: NaiveDate,
+
+    /// Shown on the date in the exported schedule
+    pub name: String,
+}
+
+impl Holiday {
+    pub fn new(date: NaiveDate, name: &str) -> Self {
+        Self { date, name: name.to_owned() }
+    }
+}
+
+/// The dates from `start` to `end`, both included, e.g. a vacation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Self {
+        Self { start, end }
+    }
+
+    /// The range of the single date.
+    pub fn day(date: NaiveDate) -> Self {
+        Self::new(date, date)
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    pub year: i32,
+    pub month: Month,
+
+    /// The number of elements to schedule on each day of the week, i.e. the
+    /// "Window Length", indexed by [`Day::to_u32`].
+    pub headcounts: [usize; 7],
+
+    /// Whether each day of the week is scheduled, indexed by [`Day::to_u32`].
+    pub working_days: [bool; 7],
+
+    /// Dates that aren't scheduled
+    pub holidays: Vec<Holiday>,
+
+    /// The shifts of each working day, in the order they start. There's
+    /// always at least one.
+    pub shifts: Vec<Shift>,
+
+    /// The roles each shift needs
+    pub coverage: Vec<Coverage>,
+}
+
+impl Default for Calendar {
+    /// The calendar of the current month.
+    fn default() -> Self {
+        let today = Local::now().date_naive();
+
+        Self::new(today.year(), Month::from_u32(today.month()))
+    }
+}
+
+impl Calendar {
+    pub fn new(year: i32, month: Month) -> Self {
+        Self {
+            year,
+            month,
+            headcounts: [DEFAULT_HEADCOUNT; 7],
+            working_days: Day::ALL.map(|day| !day.is_weekend()),
+            holidays: Vec::new(),
+            shifts: vec![Shift::default()],
+            coverage: Vec::new(),
+        }
+    }
+
+    /// Whether the day of the week is scheduled.
+    pub fn is_working_day(&self, day: Day) -> bool {
+        self.working_days[day.to_u32() as usize]
+    }
+
+    /// Sets whether the day of the week is scheduled.
+    pub fn set_working_day(&mut self, day: Day, working: bool) {
+        self.working_days[day.to_u32() as usize] = working;
+    }
+
+    /// Gets the days of the week that are scheduled, starting from Sunday.
+    pub fn working_days(&self) -> Vec<Day> {
+        Day::ALL.into_iter()
+            .filter(|day| self.is_working_day(*day))
+            .collect()
+    }
+
+    /// Gets the number of elements to schedule on the day of the week.
+    pub fn headcount(&self, day: Day) -> usize {
+        self.headcounts[day.to_u32() as usize]
+    }
+
+    /// Sets the number of elements to schedule on the day of the week.
+    pub fn set_headcount(&mut self, day: Day, headcount: usize) {
+        self.headcounts[day.to_u32() as usize] = headcount;
+    }
+
+    /// Sets the same number of elements to schedule on every day.
+    pub fn set_all_headcounts(&mut self, headcount: usize) {
+        self.headcounts = [headcount; 7];
+    }
+
+    /// Gets the number of elements to schedule on the shift, on the day of
+    /// the week.
+    pub fn shift_headcount(&self, day: Day, shift: &Shift) -> usize {
+        shift.headcount.unwrap_or_else(|| self.headcount(day))
+    }
+
+    /// Gets the number of elements to schedule on all the shifts of the day
+    /// of the week.
+    pub fn total_headcount(&self, day: Day) -> usize {
+        self.shifts.iter()
+            .map(|shift| self.shift_headcount(day, shift))
+            .sum()
+    }
+
+    /// Gets the most elements scheduled on any of the days that are
+    /// scheduled.
+    pub fn max_headcount(&self) -> usize {
+        self.working_days().into_iter()
+            .map(|day| self.total_headcount(day))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Gets the most elements scheduled on the shift, on any of the days that
+    /// are scheduled.
+    pub fn max_shift_headcount(&self, shift: &Shift) -> usize {
+        self.working_days().into_iter()
+            .map(|day| self.shift_headcount(day, shift))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Sets the shifts of each working day, or the whole day as a single
+    /// shift if `shifts` is empty.
+    pub fn set_shifts(&mut self, shifts: Vec<Shift>) {
+        self.shifts = if shifts.is_empty() {
+            vec![Shift::default()]
+        } else {
+            shifts
+        };
+    }
+
+    /// Gets the index of the shift with the `name`, ignoring the case.
+    pub fn shift_index(&self, name: &str) -> Option<usize> {
+        self.shifts.iter().position(|shift| shift.is_named(name))
+    }
+
+    /// Gets the holiday on the date, if there's one.
+    pub fn holiday(&self, date: NaiveDate) -> Option<&Holiday> {
+        self.holidays.iter().find(|holiday| holiday.date == date)
+    }
+
+    /// Gets the date of the 1-based day of the month, if the month has it.
+    pub fn date(&self, day: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(self.year, self.month.to_u32(), day)
+    }
+
+    /// Gets the first date of the month.
+    pub fn first_date(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month.to_u32(), 1)
+            .expect("the first of a month is always a valid date")
+    }
+
+    /// Gets the day of the week the month starts on.
+    pub fn first_day(&self) -> Day {
+        Day::from_u32(self.first_date().weekday().num_days_from_sunday())
+    }
+
+    /// Gets the number of days in the month.
+    pub fn num_days(&self) -> u32 {
+        self.first_date().num_days_in_month() as u32
+    }
+
+    /// Gets every date of the month.
+    pub fn dates(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        (1..=self.num_days()).filter_map(|day| self.date(day))
+    }
+
+    /// Gets the dates of each week the month spans, in calendar layout.
+    ///
+    /// Weeks start on Sunday, so a month can span four to six weeks. Days of
+    /// the first and last weeks that are outside the month are `None`.
+    pub fn weeks(&self) -> Vec<[Option<NaiveDate>; 7]> {
+        let start_day_index = self.first_day().to_u32();
+        let week_count = (start_day_index + self.num_days()).div_ceil(7);
+
+        (0..week_count)
+            .map(|week| std::array::from_fn(|j| {
+                let day = (week * 7 + j as u32 + 1)
+                    .checked_sub(start_day_index)?;
+
+                self.date(day)
+            }))
+            .collect()
+    }
+}
@@ -0,0 +1,34 @@
+//! The roles each shift needs, e.g. at least one senior.
+
+use crate::{ shift::Shift, types::Day };
+
+/// The fewest elements with a role that have to be scheduled on a shift.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coverage {
+    /// The role or skill, e.g. "Senior" or "First-aid"
+    pub role: String,
+    pub min: usize,
+
+    /// The days of the week the rule is for, every working day if it's empty
+    pub days: Vec<Day>,
+
+    /// The name of the shift the rule is for, every shift if it's `None`
+    pub shift: Option<String>,
+}
+
+impl Coverage {
+    pub fn new(role: &str, min: usize) -> Self {
+        Self {
+            role: role.trim().to_owned(),
+            min,
+            days: Vec::new(),
+            shift: None,
+        }
+    }
+
+    /// Whether the rule is for the shift on the day of the week.
+    pub fn applies_to(&self, day: Day, shift: &Shift) -> bool {
+        (self.days.is_empty() || self.days.contains(&day))
+            && self.shift.as_ref().is_none_or(|name| shift.is_named(name))
+    }
+}
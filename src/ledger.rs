@@ -0,0 +1,46 @@
+//! The fairness debts carried over from one period to the next.
+
+/// The name of the sheet the ledger is written to in an excel export
+pub const LEDGER_SHEET: &str = "Ledger";
+
+/// The ledger of a single element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LedgerEntry {
+    /// The name of the element
+    pub name: String,
+
+    /// The number of days the element is owed, negative if it was scheduled
+    /// more than its share
+    pub debt: i32,
+
+    /// Position of the element in the rotation, where `0` is the element the
+    /// next period's rotation starts at
+    pub rotation: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Ledger {
+    pub entries: Vec<LedgerEntry>,
+}
+
+impl Ledger {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Gets the entry of the element with the `name`, if there's one.
+    pub fn entry(&self, name: &str) -> Option<&LedgerEntry> {
+        self.entries.iter().find(|entry| entry.name == name.trim())
+    }
+
+    /// Gets the debt of the element with the `name`, `0` if it's not in the
+    /// ledger.
+    pub fn debt(&self, name: &str) -> i32 {
+        self.entry(name).map(|entry| entry.debt).unwrap_or_default()
+    }
+
+    /// Gets the position of the element with the `name` in the rotation.
+    pub fn rotation(&self, name: &str) -> Option<usize> {
+        self.entry(name).and_then(|entry| entry.rotation)
+    }
+}
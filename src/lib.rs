@@ -0,0 +1,25 @@
+//! Creates schedules and shift calendars as excel files.
+//!
+//! The elements to schedule are read with [`reader::Reader`], checked with
+//! [`validator::validate`], scheduled for a [`calendar::Calendar`] month with
+//! a [`scheduler::Scheduler`], e.g. the rotation of [`worker::process`], into
+//! a [`schedule::Schedule`] and exported with
+//! [`writer::Writer`].
+
+pub mod calendar;
+pub mod coverage;
+pub mod ledger;
+pub mod pairing;
+pub mod reader;
+pub mod schedule;
+pub mod scheduler;
+pub mod shift;
+pub mod solver;
+pub mod stats;
+pub mod strategies;
+pub mod trace;
+pub mod types;
+pub mod ui;
+pub mod validator;
+pub mod worker;
+pub mod writer;
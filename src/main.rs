@@ -1,20 +1,14 @@
 //#![windows_subsystem = "windows"]
-mod ui;
-mod reader;
-mod writer;
-mod worker;
-mod types;
-
 use iced::{
     application, window::Settings, Result as IcedResult, Size, Theme
 };
 
-use ui::Window;
+use schedulez::ui::Window;
 
 fn main() -> IcedResult {
     application("SchedulEZ", Window::update, Window::view)
         .window(Settings {
-            size: Size { width: 640.0, height: 480.0 },
+            size: Size { width: 800.0, height: 600.0 },
             resizable: false,
             transparent: true,
             ..Settings::default()
@@ -22,4 +16,3 @@ fn main() -> IcedResult {
         .theme(|_| Theme::Dark)
         .run()
 }
-
@@ -0,0 +1,68 @@
+//! Elements that have to be scheduled together, or never together.
+
+use std::fmt;
+
+/// Whether the two elements of a pair share their days or never do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairRule {
+    /// Scheduled on the same dates, e.g. a mentor and their mentee
+    Together,
+
+    /// Never scheduled on the same date, e.g. two key-holders
+    Apart,
+}
+
+impl PairRule {
+    /// Gets the rule from its name in the input, accepting the common
+    /// synonyms.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_uppercase()
+            .replace(['_', '-'], " ");
+
+        match name.as_str() {
+            "TOGETHER" | "MUST" | "MUST WORK TOGETHER" | "ALWAYS" | "SAME DAYS"
+                | "MENTOR" | "PAIR" => Some(Self::Together),
+
+            "APART" | "NEVER" | "NEVER TOGETHER" | "SEPARATE" | "NOT TOGETHER"
+                | "CONFLICT" => Some(Self::Apart),
+
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Together => "Together",
+            Self::Apart => "Apart",
+        }
+    }
+}
+
+impl fmt::Display for PairRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A rule for a pair of elements, read from the "Pairs" sheet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pairing {
+    /// Names of the elements of the pair
+    pub first: String,
+    pub second: String,
+    pub rule: PairRule,
+
+    /// The (1-based) row of the sheet this was read from
+    pub row: usize,
+}
+
+impl Pairing {
+    pub fn new(first: &str, second: &str, rule: PairRule) -> Self {
+        Self {
+            first: first.trim().to_owned(),
+            second: second.trim().to_owned(),
+            rule,
+            row: 0,
+        }
+    }
+}
@@ -1,17 +1,757 @@
-use std::path::Path;
+use std::{ error::Error, fmt, io, iter, path::Path };
 
-use calamine::{ Reader as CalamineReader, open_workbook, Xlsx, Data };
+use calamine::{
+    Reader as CalamineReader, open_workbook, Data, Xlsx, XlsxError,
+};
+use chrono::{ NaiveDate, NaiveTime };
+use csv::{ ErrorKind as CsvErrorKind, ReaderBuilder, Trim };
 
-use crate::types::Day;
+use crate::{
+    calendar::{ DateRange, Holiday }, coverage::Coverage,
+    ledger::{ Ledger, LedgerEntry, LEDGER_SHEET }, pairing::{ Pairing, PairRule },
+    shift::Shift, types::{ Day, DayNames, FileExt },
+};
+
+/// Formats the dates in the input can be written in, other than excel dates.
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%Y/%m/%d", "%d.%m.%Y"];
+
+/// Formats the times of the shifts can be written in, other than excel times.
+const TIME_FORMATS: [&str; 3] = ["%H:%M", "%H:%M:%S", "%I:%M %p"];
+
+/// Errors that can occur while reading the input file.
+#[derive(Debug)]
+pub enum ReadError {
+    /// The file couldn't be opened or read.
+    Io(io::Error),
+
+    /// The file isn't an .xlsx or .csv file, or its contents are not valid for
+    /// its format.
+    UnsupportedFormat(String),
+
+    /// The header row doesn't have a column that's required.
+    MissingColumn(&'static str),
+
+    /// The workbook doesn't have a sheet that's required, e.g. the "Ledger"
+    /// sheet of a previous export.
+    MissingSheet(&'static str),
+
+    /// A day, range of days or group of days in the "Prefer Days" or
+    /// "Avoid Days" column is not recognized.
+    ///
+    /// `row` and `column` are 1-based, like in the spreadsheet. These don't
+    /// stop the file from being read, they're collected in
+    /// [`Reader::row_errors`] instead.
+    BadDayToken { row: usize, column: usize, token: String },
+
+    /// A date, e.g. in the "Holidays" sheet, is not recognized.
+    ///
+    /// Like [`ReadError::BadDayToken`], these are collected in
+    /// [`Reader::row_errors`].
+    BadDate { row: usize, column: usize, value: String },
+
+    /// A time, e.g. the start of a shift, is not recognized.
+    ///
+    /// Like [`ReadError::BadDayToken`], these are collected in
+    /// [`Reader::row_errors`].
+    BadTime { row: usize, column: usize, value: String },
+
+    /// The rule of a pair in the "Pairs" sheet is neither "Together" nor
+    /// "Apart", or one of their synonyms.
+    ///
+    /// Like [`ReadError::BadDayToken`], these are collected in
+    /// [`Reader::row_errors`].
+    BadPairRule { row: usize, column: usize, value: String },
+
+    /// A number, e.g. a debt in the "Ledger" sheet, is not recognized.
+    ///
+    /// Like [`ReadError::BadDayToken`], these are collected in
+    /// [`Reader::row_errors`].
+    BadNumber { row: usize, column: usize, value: String },
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Cannot read the file: {}", e),
+
+            Self::UnsupportedFormat(reason) => {
+                write!(f, "Unsupported file: {}", reason)
+            }
+
+            Self::MissingColumn(column) => {
+                write!(f, "The \"{}\" column is missing", column)
+            }
+
+            Self::MissingSheet(sheet) => {
+                write!(f, "The \"{}\" sheet is missing", sheet)
+            }
+
+            Self::BadDayToken { row, column, token } => write!(
+                f,
+                "Unknown day \"{}\" at row {}, column {}",
+                token,
+                row,
+                column_name(*column),
+            ),
+
+            Self::BadDate { row, column, value } => write!(
+                f,
+                "Unknown date \"{}\" at row {}, column {}",
+                value,
+                row,
+                column_name(*column),
+            ),
+
+            Self::BadTime { row, column, value } => write!(
+                f,
+                "Unknown time \"{}\" at row {}, column {}",
+                value,
+                row,
+                column_name(*column),
+            ),
+
+            Self::BadPairRule { row, column, value } => write!(
+                f,
+                "Unknown pair rule \"{}\" at row {}, column {}",
+                value,
+                row,
+                column_name(*column),
+            ),
+
+            Self::BadNumber { row, column, value } => write!(
+                f,
+                "Not a number \"{}\" at row {}, column {}",
+                value,
+                row,
+                column_name(*column),
+            ),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self { Self::Io(e) }
+}
+
+impl From<XlsxError> for ReadError {
+    fn from(e: XlsxError) -> Self {
+        match e {
+            XlsxError::Io(e) => Self::Io(e),
+            e => Self::UnsupportedFormat(e.to_string()),
+        }
+    }
+}
+
+impl From<csv::Error> for ReadError {
+    fn from(e: csv::Error) -> Self {
+        if !e.is_io_error() {
+            return Self::UnsupportedFormat(e.to_string());
+        }
+
+        match e.into_kind() {
+            CsvErrorKind::Io(e) => Self::Io(e),
+            kind => Self::UnsupportedFormat(format!("{:?}", kind)),
+        }
+    }
+}
+
+/// Parses a date written in one of the [`DATE_FORMATS`].
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    DATE_FORMATS.iter()
+        .find_map(|format| NaiveDate::parse_from_str(value.trim(), format).ok())
+}
+
+/// Parses a date, or a range of dates like "2026-11-03..2026-11-07", written
+/// in one of the [`DATE_FORMATS`].
+///
+/// Returns `None` if a date isn't recognized or the range ends before it
+/// starts.
+pub fn parse_date_range(value: &str) -> Option<DateRange> {
+    let value = value.trim();
+
+    let bounds = value.split_once("..")
+        .or_else(|| value.split_once('–'))
+        .or_else(|| value.split_once(" to "));
+
+    let range = match bounds {
+        Some((start, end)) => DateRange::new(parse_date(start)?, parse_date(end)?),
+        None => DateRange::day(parse_date(value)?),
+    };
+
+    (range.start <= range.end).then_some(range)
+}
+
+/// Parses a number of days or elements, e.g. "8". Excel numbers can come as
+/// "8.0", so they're rounded.
+pub fn parse_count(value: &str) -> Option<usize> {
+    value.trim().parse::<f64>().ok()
+        .filter(|n| n.is_finite() && *n >= 0.0)
+        .map(|n| n.round() as usize)
+}
+
+/// Parses a comma separated list of dates and ranges of dates.
+///
+/// Dates that aren't recognized are skipped and added to `errors`, with the
+/// 1-based `row` and the 0-based `column` they're in.
+fn parse_date_ranges(
+    data: &str,
+    row: usize,
+    column: usize,
+    errors: &mut Vec<ReadError>,
+) -> Vec<DateRange> {
+    let mut ranges = Vec::new();
+
+    for value in data.split(",").map(|s| s.trim()) {
+        if value.is_empty() {
+            continue;
+        }
+
+        match parse_date_range(value) {
+            Some(range) => ranges.push(range),
+
+            None => errors.push(ReadError::BadDate {
+                row,
+                column: column + 1,
+                value: value.to_owned(),
+            }),
+        }
+    }
+
+    ranges
+}
+
+/// Parses a comma separated list of days, ranges of days and groups of days.
+///
+/// Days that aren't recognized are skipped and added to `errors`, with the
+/// 1-based `row` and the 0-based `column` they're in.
+fn parse_days(
+    day_names: &DayNames,
+    data: &str,
+    row: usize,
+    column: usize,
+    errors: &mut Vec<ReadError>,
+) -> Vec<Day> {
+    let mut days = Vec::new();
+
+    for token in data.split(",").map(|s| s.trim()) {
+        if token.is_empty() {
+            continue;
+        }
+
+        match day_names.parse(token) {
+            Ok(parsed) => {
+                for day in parsed {
+                    if !days.contains(&day) {
+                        days.push(day);
+                    }
+                }
+            }
+
+            Err(_) => errors.push(ReadError::BadDayToken {
+                row,
+                column: column + 1,
+                token: token.to_owned(),
+            }),
+        }
+    }
+
+    days
+}
+
+/// Parses a time written in one of the [`TIME_FORMATS`], e.g. "14:00". The
+/// minutes can be left out, e.g. "6 AM".
+pub fn parse_time(value: &str) -> Option<NaiveTime> {
+    let mut value = value.trim().to_uppercase();
+
+    if !value.contains(':') {
+        let hour_len = value.find(' ').unwrap_or(value.len());
+        value.insert_str(hour_len, ":00");
+    }
+
+    TIME_FORMATS.iter()
+        .find_map(|format| NaiveTime::parse_from_str(&value, format).ok())
+}
+
+/// Gets the text of a cell, with excel dates written as "YYYY-MM-DD" and
+/// excel times as "HH:MM".
+fn cell_text(cell: &Data) -> String {
+    match cell {
+        // Times alone are fractions of the first day
+        Data::DateTime(dt) if dt.is_datetime() && dt.as_f64() < 1.0 => dt
+            .as_datetime()
+            .map(|datetime| datetime.format("%H:%M").to_string())
+            .unwrap_or_else(|| cell.to_string()),
+
+        Data::DateTime(dt) if dt.is_datetime() => dt.as_datetime()
+            .map(|datetime| datetime.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| cell.to_string()),
+
+        _ => cell.to_string(),
+    }
+}
+
+/// Gets the spreadsheet name of the 1-based column number, e.g. "C" for 3.
+pub fn column_name(column: usize) -> String {
+    let mut name = String::new();
+    let mut n = column;
+
+    while n > 0 {
+        let rem = (n - 1) % 26;
+        name.insert(0, (b'A' + rem as u8) as char);
+        n = (n - 1) / 26;
+    }
+
+    name
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct ScheduledElement {
     pub text: String,
+
+    /// The (1-based) row of the input this element was read from
+    pub row: usize,
+
     pub prefer_days: Vec<Day>,
     pub avoid_days: Vec<Day>,
+
+    /// Names of the shifts this element prefers, checked against the shifts
+    /// of the calendar when it's validated
+    pub prefer_shifts: Vec<String>,
+
+    /// Names of the shifts this element can't be scheduled on
+    pub avoid_shifts: Vec<String>,
+
+    /// The dates this element can't be scheduled on, e.g. planned leave
+    pub leave: Vec<DateRange>,
+
+    /// The most dates this element can be scheduled on in the period
+    pub max_assignments: Option<usize>,
+
+    /// The fewest dates this element should be scheduled on in the period
+    pub min_assignments: Option<usize>,
+
+    /// The roles or skills of this element, e.g. "Senior" or "Spanish"
+    pub roles: Vec<String>,
+
+    /// Values of the columns that aren't used for scheduling, e.g. "Email" or
+    /// "Team", as `(header, value)` pairs in the order they appear.
+    pub metadata: Vec<(String, String)>,
 }
 
-#[derive(Debug, Default, Clone)]
+impl ScheduledElement {
+    /// Whether this element has the role, ignoring the case.
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r.eq_ignore_ascii_case(role.trim()))
+    }
+}
+
+/// The kinds of sheets the input can have.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Sheet {
+    /// The elements to schedule
+    Elements,
+
+    /// The dates that aren't scheduled
+    Holidays,
+
+    /// The debts and rotation carried over from the previous period
+    Ledger,
+
+    /// The shifts of each working day
+    Shifts,
+
+    /// The dates each element is on leave
+    Leave,
+
+    /// The roles each shift needs
+    Coverage,
+
+    /// The elements that are scheduled together or never together
+    Pairs,
+}
+
+impl Sheet {
+    /// Gets the kind of the sheet from its name.
+    fn from_name(name: &str) -> Self {
+        match name.trim().to_uppercase().as_str() {
+            "HOLIDAYS" | "HOLIDAY" | "PUBLIC HOLIDAYS" => Self::Holidays,
+            "LEDGER" | "DEBTS" => Self::Ledger,
+            "SHIFTS" | "SHIFT" => Self::Shifts,
+            "LEAVE" | "VACATIONS" | "ABSENCES" | "TIME OFF" => Self::Leave,
+            "COVERAGE" | "REQUIREMENTS" => Self::Coverage,
+            "PAIRS" | "PAIRINGS" | "PAIRING" => Self::Pairs,
+            _ => Self::Elements,
+        }
+    }
+}
+
+/// Where the columns of the "Holidays" sheet are.
+#[derive(Debug, Clone)]
+struct HolidayColumns {
+    date: usize,
+    name: Option<usize>,
+}
+
+impl Default for HolidayColumns {
+    /// Without a header row, the columns are Date and Name in that order.
+    fn default() -> Self {
+        Self { date: 0, name: Some(1) }
+    }
+}
+
+impl HolidayColumns {
+    /// Builds the holiday columns from a header row.
+    ///
+    /// Returns `None` if the row doesn't have a date column, i.e. it's not a
+    /// header row.
+    fn from_header_row(row: &[String]) -> Option<Self> {
+        let find = |headers: &[&str]| row.iter()
+            .position(|cell| headers.contains(
+                &cell.trim().to_uppercase().as_str()
+            ));
+
+        Some(Self {
+            date: find(&["DATE", "DATES"])?,
+            name: find(&["NAME", "HOLIDAY", "DESCRIPTION", "OCCASION"]),
+        })
+    }
+}
+
+/// Where the columns of the "Shifts" sheet are.
+#[derive(Debug, Clone)]
+struct ShiftColumns {
+    name: usize,
+    start: usize,
+    end: usize,
+    headcount: Option<usize>,
+}
+
+impl Default for ShiftColumns {
+    /// Without a header row, the columns are Name, Start, End and Headcount in
+    /// that order.
+    fn default() -> Self {
+        Self { name: 0, start: 1, end: 2, headcount: Some(3) }
+    }
+}
+
+impl ShiftColumns {
+    /// Builds the shift columns from a header row.
+    ///
+    /// Returns `None` if the row doesn't have the name, start and end
+    /// columns, i.e. it's not a header row.
+    fn from_header_row(row: &[String]) -> Option<Self> {
+        let find = |headers: &[&str]| row.iter()
+            .position(|cell| headers.contains(
+                &cell.trim().to_uppercase().as_str()
+            ));
+
+        Some(Self {
+            name: find(&["NAME", "SHIFT", "SHIFT NAME"])?,
+            start: find(&["START", "STARTS", "FROM", "START TIME"])?,
+            end: find(&["END", "ENDS", "TO", "UNTIL", "END TIME"])?,
+            headcount: find(&["HEADCOUNT", "PEOPLE", "COUNT", "STAFF"]),
+        })
+    }
+}
+
+/// Where the columns of the "Leave" sheet are.
+#[derive(Debug, Clone)]
+struct LeaveColumns {
+    name: usize,
+    dates: usize,
+}
+
+impl Default for LeaveColumns {
+    /// Without a header row, the columns are Name and Dates in that order.
+    fn default() -> Self {
+        Self { name: 0, dates: 1 }
+    }
+}
+
+impl LeaveColumns {
+    /// Builds the leave columns from a header row.
+    ///
+    /// Returns `None` if the row doesn't have both a name and a dates column,
+    /// i.e. it's not a header row.
+    fn from_header_row(row: &[String]) -> Option<Self> {
+        let find = |headers: &[&str]| row.iter()
+            .position(|cell| headers.contains(
+                &cell.trim().to_uppercase().as_str()
+            ));
+
+        Some(Self {
+            name: find(&["NAME", "NAMES", "ELEMENT", "EMPLOYEE", "PERSON"])?,
+            dates: find(&["DATES", "DATE", "LEAVE", "FROM..TO", "PERIOD"])?,
+        })
+    }
+}
+
+/// Where the columns of the "Coverage" sheet are.
+#[derive(Debug, Clone)]
+struct CoverageColumns {
+    role: usize,
+    min: usize,
+    days: Option<usize>,
+    shift: Option<usize>,
+}
+
+impl Default for CoverageColumns {
+    /// Without a header row, the columns are Role, Min, Days and Shift in
+    /// that order.
+    fn default() -> Self {
+        Self { role: 0, min: 1, days: Some(2), shift: Some(3) }
+    }
+}
+
+impl CoverageColumns {
+    /// Builds the coverage columns from a header row.
+    ///
+    /// Returns `None` if the row doesn't have both a role and a minimum
+    /// column, i.e. it's not a header row.
+    fn from_header_row(row: &[String]) -> Option<Self> {
+        let find = |headers: &[&str]| row.iter()
+            .position(|cell| headers.contains(
+                &cell.trim().to_uppercase().as_str()
+            ));
+
+        Some(Self {
+            role: find(&["ROLE", "ROLES", "SKILL", "SKILLS"])?,
+            min: find(&["MIN", "MINIMUM", "AT LEAST", "COUNT"])?,
+            days: find(&["DAYS", "DAY"]),
+            shift: find(&["SHIFT", "SHIFTS"]),
+        })
+    }
+}
+
+/// Where the columns of the "Pairs" sheet are.
+#[derive(Debug, Clone)]
+struct PairColumns {
+    first: usize,
+    second: usize,
+    rule: usize,
+}
+
+impl Default for PairColumns {
+    /// Without a header row, the columns are Name, With and Rule in that
+    /// order.
+    fn default() -> Self {
+        Self { first: 0, second: 1, rule: 2 }
+    }
+}
+
+impl PairColumns {
+    /// Builds the pair columns from a header row.
+    ///
+    /// Returns `None` if the row doesn't have the columns of both names and
+    /// the rule, i.e. it's not a header row.
+    fn from_header_row(row: &[String]) -> Option<Self> {
+        let find = |headers: &[&str]| row.iter()
+            .position(|cell| headers.contains(
+                &cell.trim().to_uppercase().as_str()
+            ));
+
+        Some(Self {
+            first: find(&["NAME", "FIRST", "PERSON", "EMPLOYEE", "ELEMENT"])?,
+            second: find(&["WITH", "SECOND", "PARTNER", "OTHER"])?,
+            rule: find(&["RULE", "TYPE", "KIND", "PAIRING"])?,
+        })
+    }
+}
+
+/// What a list of days read from the input is for.
+#[derive(Debug, Clone, Copy)]
+enum DayList {
+    /// The preferred days of the element at the index
+    PreferDays(usize),
+
+    /// The avoided days of the element at the index
+    AvoidDays(usize),
+
+    /// The days of the coverage rule at the index
+    Coverage(usize),
+}
+
+/// Leave of an element read from the "Leave" sheet.
+#[derive(Debug, Clone)]
+pub struct Leave {
+    /// The name of the element that's on leave
+    pub name: String,
+
+    /// The (1-based) row of the sheet this was read from
+    pub row: usize,
+
+    pub dates: DateRange,
+}
+
+/// Where the columns of the "Ledger" sheet are.
+#[derive(Debug, Clone)]
+struct LedgerColumns {
+    name: usize,
+    debt: usize,
+    rotation: Option<usize>,
+}
+
+impl Default for LedgerColumns {
+    /// Without a header row, the columns are Name, Debt and Rotation in that
+    /// order, like in the exports.
+    fn default() -> Self {
+        Self { name: 0, debt: 1, rotation: Some(2) }
+    }
+}
+
+impl LedgerColumns {
+    /// Builds the ledger columns from a header row.
+    ///
+    /// Returns `None` if the row doesn't have both a name and a debt column,
+    /// i.e. it's not a header row.
+    fn from_header_row(row: &[String]) -> Option<Self> {
+        let find = |headers: &[&str]| row.iter()
+            .position(|cell| headers.contains(
+                &cell.trim().to_uppercase().as_str()
+            ));
+
+        Some(Self {
+            name: find(&["NAME", "NAMES", "ELEMENT", "EMPLOYEE", "PERSON"])?,
+            debt: find(&["DEBT", "DEBTS", "OWED"])?,
+            rotation: find(&["ROTATION", "POSITION", "ORDER"]),
+        })
+    }
+}
+
+/// The columns of the input that are used for scheduling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Name,
+    PreferDays,
+    AvoidDays,
+    PreferShifts,
+    AvoidShifts,
+    Leave,
+    MaxAssignments,
+    MinAssignments,
+    Roles,
+}
+
+impl Column {
+    /// Gets the column from its header, accepting the common synonyms.
+    fn from_header(header: &str) -> Option<Self> {
+        let header = header.to_uppercase()
+            .replace(['_', '-'], " ")
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        match header.as_str() {
+            "NAME" | "NAMES" | "FULL NAME" | "ELEMENT" | "ELEMENTS"
+                | "EMPLOYEE" | "EMPLOYEE NAME" | "PERSON" | "MEMBER"
+                => Some(Self::Name),
+
+            "PREFER DAYS" | "PREFER" | "PREFERRED DAYS" | "PREFERRED"
+                | "PREFERENCES"
+                => Some(Self::PreferDays),
+
+            "AVOID DAYS" | "AVOID" | "AVOIDED DAYS" | "UNAVAILABLE DAYS"
+                | "DAYS OFF"
+                => Some(Self::AvoidDays),
+
+            "PREFER SHIFTS" | "PREFERRED SHIFTS" | "PREFERRED SHIFT"
+                => Some(Self::PreferShifts),
+
+            "AVOID SHIFTS" | "AVOIDED SHIFTS" | "UNAVAILABLE SHIFTS"
+                => Some(Self::AvoidShifts),
+
+            "LEAVE" | "LEAVE DATES" | "VACATION" | "VACATIONS" | "ABSENCES"
+                | "TIME OFF" | "UNAVAILABLE DATES"
+                => Some(Self::Leave),
+
+            "MAX ASSIGNMENTS" | "MAX DAYS" | "MAXIMUM DAYS" | "MAX"
+                | "MAXIMUM ASSIGNMENTS"
+                => Some(Self::MaxAssignments),
+
+            "MIN ASSIGNMENTS" | "MIN DAYS" | "MINIMUM DAYS" | "MIN"
+                | "MINIMUM ASSIGNMENTS"
+                => Some(Self::MinAssignments),
+
+            "ROLES" | "ROLE" | "SKILLS" | "SKILL" | "QUALIFICATIONS"
+                => Some(Self::Roles),
+
+            _ => None,
+        }
+    }
+}
+
+/// Where each column is in the input.
+#[derive(Debug, Clone)]
+struct ColumnMap {
+    known: Vec<(usize, Column)>,
+
+    /// Columns that aren't known, kept as metadata along with their header
+    extra: Vec<(usize, String)>,
+}
+
+impl Default for ColumnMap {
+    /// Without a header row, the columns are Name, Prefer Days and Avoid Days
+    /// in that order.
+    fn default() -> Self {
+        Self {
+            known: vec![
+                (0, Column::Name),
+                (1, Column::PreferDays),
+                (2, Column::AvoidDays),
+            ],
+            extra: Vec::new(),
+        }
+    }
+}
+
+impl ColumnMap {
+    /// Builds the column map from a header row.
+    ///
+    /// Returns `None` if it's not a header row, i.e. it has neither the name
+    /// header nor two other known headers. A single known header could just
+    /// be a value, e.g. "Preferred" in a notes column.
+    fn from_header_row(row: &[String]) -> Result<Option<Self>, ReadError> {
+        let mut known = Vec::new();
+        let mut extra = Vec::new();
+
+        for (i, cell) in row.iter().enumerate() {
+            match Column::from_header(cell) {
+                // Only the first occurence of a column is used.
+                Some(column) if !known.iter().any(|(_, c)| *c == column) => {
+                    known.push((i, column));
+                }
+
+                Some(_) => {}
+
+                None if !cell.trim().is_empty() => {
+                    extra.push((i, cell.trim().to_owned()));
+                }
+
+                None => {}
+            }
+        }
+
+        if !known.iter().any(|(_, c)| *c == Column::Name) {
+            return match known.len() {
+                0 | 1 => Ok(None),
+                _ => Err(ReadError::MissingColumn("Name")),
+            };
+        }
+
+        Ok(Some(Self { known, extra }))
+    }
+}
+
+#[derive(Debug, Default)]
 pub struct Reader {
     /// Name of the elements that are being schedules
     ///
@@ -19,101 +759,675 @@ pub struct Reader {
     /// "Scheduled Elements" cell.
     pub elements_name: String,
     pub elements: Vec<ScheduledElement>,
+
+    /// Problems in individual rows that didn't stop the file from being read,
+    /// e.g. unknown days.
+    pub row_errors: Vec<ReadError>,
+
+    /// Names of the days in the language of the input
+    pub day_names: DayNames,
+
+    /// The dates that aren't scheduled, read from the "Holidays" sheet or a
+    /// separate file
+    pub holidays: Vec<Holiday>,
+
+    /// The debts and rotation of the previous period, read from a "Ledger"
+    /// sheet or a previous export
+    pub ledger: Ledger,
+
+    /// The shifts of each working day, read from the "Shifts" sheet or a
+    /// separate file
+    pub shifts: Vec<Shift>,
+
+    /// The leave read from the "Leave" sheet, also added to the
+    /// [`ScheduledElement::leave`] of the elements with the same name
+    pub leave: Vec<Leave>,
+
+    /// The roles each shift needs, read from the "Coverage" sheet or a
+    /// separate file
+    pub coverage: Vec<Coverage>,
+
+    /// The elements that are scheduled together or never together, read from
+    /// the "Pairs" sheet
+    pub pairings: Vec<Pairing>,
+    columns: ColumnMap,
+
+    /// Whether a row of elements was read, after which no row is taken for
+    /// the header row
+    elements_started: bool,
+
+    /// The lists of days as they were read, to parse them again when the
+    /// working days change
+    day_lists: Vec<(DayList, String)>,
+    holiday_columns: HolidayColumns,
+    ledger_columns: LedgerColumns,
+    shift_columns: ShiftColumns,
+    leave_columns: LeaveColumns,
+    coverage_columns: CoverageColumns,
+    pair_columns: PairColumns,
 }
 
 impl Reader {
-    pub fn read(&mut self, file_path: &Path) {
-        println!("Reading workbook {}", file_path.to_str().unwrap());
+    /// Reads the elements from the file.
+    ///
+    /// In an excel workbook, the elements are read from the first non-empty
+    /// sheet, and the holidays from a sheet named "Holidays" if there's one.
+    pub fn read(&mut self, file_path: &Path) -> Result<(), ReadError> {
+        self.read_file(file_path, None)?;
 
-        let mut workbook: Xlsx<_> = open_workbook(file_path)
-            .expect("cannot open file!");
+        // The "Leave" sheet can come before the elements it refers to
+        for leave in self.leave.iter() {
+            if let Some(elem) = self.elements.iter_mut()
+                .find(|elem| elem.text.trim() == leave.name)
+            {
+                elem.leave.push(leave.dates);
+            }
+        }
 
-        println!("Reading workbook {}", file_path.to_str().unwrap());
+        Ok(())
+    }
 
-        for (sheet, range) in workbook.worksheets().iter() {
-            println!("Reading sheet {}", sheet);
+    /// Sets the working days the groups of days, e.g. "weekdays", are made
+    /// of, and parses the days read so far again with them.
+    pub fn set_working_days(&mut self, working_days: [bool; 7]) {
+        self.day_names.working_days = working_days;
 
-            if !range.is_empty() && range.used_cells().count() > 0 {
-                let mut data_found = false;
-                let mut row_index: u32 = 0;
+        // The bad days were reported when the lists were first read
+        let mut errors = Vec::new();
 
-                // for cell in range.cells() {
-                //     println!("{},{}: {}", cell.0, cell.1, cell.2);
-                // }
-                let mut name_col_found;
-                let mut prefer_col_found;
+        for (list, data) in self.day_lists.iter() {
+            let days = parse_days(&self.day_names, data, 0, 0, &mut errors);
 
-                for (i,row) in range.rows().enumerate() {
-                    if row.is_empty() {
-                        continue;
-                    }
+            match *list {
+                DayList::PreferDays(id) => self.elements[id].prefer_days = days,
+                DayList::AvoidDays(id) => self.elements[id].avoid_days = days,
+                DayList::Coverage(id) => self.coverage[id].days = days,
+            }
+        }
+    }
 
-                    // If it's the header row, regardless if it's on top or
-                    // not, ignore this row
-                    name_col_found = false;
-                    prefer_col_found = false;
+    /// Reads the holidays from a separate file, e.g. a holiday calendar shared
+    /// by every team.
+    pub fn read_holidays(&mut self, file_path: &Path) -> Result<(), ReadError> {
+        self.holiday_columns = HolidayColumns::default();
+        self.read_file(file_path, Some(Sheet::Holidays))
+    }
 
-                    if let Data::String(first_col) = row[0].clone() {
-                        if first_col.to_uppercase() == "NAME" {
-                            name_col_found = true;
-                        }
-                    }
+    /// Reads the shifts from a separate file, replacing the ones read before.
+    pub fn read_shifts(&mut self, file_path: &Path) -> Result<(), ReadError> {
+        self.shifts = Vec::new();
+        self.shift_columns = ShiftColumns::default();
+        self.read_file(file_path, Some(Sheet::Shifts))
+    }
 
-                    if name_col_found {
-                        if let Data::String(first_col) = row[1].clone() {
-                            if first_col.to_uppercase() == "PREFER DAYS"
-                                || first_col.to_uppercase() == "PREFER_DAYS"
-                                || first_col.to_uppercase() == "PREFER" {
-                                prefer_col_found = true;
-                            }
-                        }
-                    }
+    /// Reads the coverage rules from a separate file, replacing the ones read
+    /// before.
+    pub fn read_coverage(&mut self, file_path: &Path) -> Result<(), ReadError> {
+        self.coverage = Vec::new();
+        self.day_lists.retain(|(list, _)| !matches!(list, DayList::Coverage(_)));
+        self.coverage_columns = CoverageColumns::default();
+        self.read_file(file_path, Some(Sheet::Coverage))
+    }
 
-                    if prefer_col_found {
-                        if let Data::String(first_col) = row[2].clone() {
-                            if first_col.to_uppercase() == "AVOID DAYS"
-                                || first_col.to_uppercase() == "AVOID_DAYS"
-                                || first_col.to_uppercase() == "AVOID" {
-                                continue;
-                            }
-                        }
-                    }
+    /// Reads the ledger of the previous period, from the "Ledger" sheet of
+    /// its excel export or from a separate .csv file.
+    ///
+    /// Replaces the ledger read before, if any. Unlike the other separate
+    /// files, a workbook without a "Ledger" sheet is an error, since its first
+    /// sheet would be the calendar of an older export.
+    pub fn read_ledger(&mut self, file_path: &Path) -> Result<(), ReadError> {
+        self.ledger = Ledger::default();
+        self.ledger_columns = LedgerColumns::default();
+        self.read_file(file_path, Some(Sheet::Ledger))
+    }
 
-                    let mut element = ScheduledElement::default();
-
-                    // Actual data insertion takes place here:
-                    for (j,cell) in row.iter().enumerate() {
-                        match cell {
-                            calamine::Data::String(data) => {
-                                if j == 0 {
-                                    element.text = data.clone();
-                                }
-
-                                if j == 1 {
-                                    element.prefer_days = data.split(",")
-                                        .map(|s| Day::from(s.trim()))
-                                        .collect();
-                                }
-
-                                if j == 2 {
-                                    element.avoid_days = data.split(",")
-                                        .map(|s| Day::from(s.trim()))
-                                        .collect();
-                                }
-                            }
-
-                            _ => {}
-                        }
-                    }
+    /// Reads the file, with all of its data being of the `sheet` kind if it's
+    /// given.
+    fn read_file(
+        &mut self,
+        file_path: &Path,
+        sheet: Option<Sheet>,
+    ) -> Result<(), ReadError> {
+        match FileExt::from_path(file_path) {
+            Some(FileExt::Xlsx) => self.read_xlsx(file_path, sheet),
 
-                    println!("{:?}", element);
+            Some(FileExt::Csv) => self.read_csv(
+                file_path, sheet.unwrap_or(Sheet::Elements)
+            ),
 
-                    self.elements.push(element);
+            None => Err(ReadError::UnsupportedFormat(
+                "only .xlsx and .csv files can be read".to_owned()
+            )),
+        }
+    }
+
+    /// Reads the worksheets of an excel workbook.
+    ///
+    /// If `only` is given, just the sheet named like that kind is read, or the
+    /// first non-empty sheet if there's no such sheet, except for the ledger.
+    fn read_xlsx(
+        &mut self,
+        file_path: &Path,
+        only: Option<Sheet>,
+    ) -> Result<(), ReadError> {
+        let mut workbook: Xlsx<_> = open_workbook(file_path)?;
+        let mut elements_read = false;
+        let worksheets = workbook.worksheets();
+
+        let named = only.filter(|kind| worksheets.iter()
+            .any(|(name, _)| Sheet::from_name(name) == *kind));
+
+        if only == Some(Sheet::Ledger) && named.is_none() {
+            return Err(ReadError::MissingSheet(LEDGER_SHEET));
+        }
+
+        for (name, range) in worksheets {
+            if range.used_cells().count() == 0
+                || named.is_some_and(|kind| Sheet::from_name(&name) != kind)
+            {
+                continue;
+            }
+
+            let sheet = only.unwrap_or_else(|| Sheet::from_name(&name));
+
+            // Only the first sheet of elements is read
+            if sheet == Sheet::Elements {
+                if elements_read {
+                    continue;
                 }
+
+                elements_read = true;
+            }
+
+            // The range doesn't always start at "A1", pad the rows so that
+            // the indices match the sheet's columns.
+            let (start_row, start_col) = range.start().unwrap_or_default();
+
+            for (i, row) in range.rows().enumerate() {
+                let row: Vec<String> = iter::repeat_n(
+                    String::new(), start_col as usize
+                )
+                    .chain(row.iter().map(cell_text))
+                    .collect();
+
+                self.read_row(sheet, start_row as usize + i + 1, &row)?;
             }
 
-            break;
+            if only.is_some() {
+                break;
+            }
         }
+
+        Ok(())
     }
-}
 
+    /// Reads a comma separated file.
+    ///
+    /// Fields can be quoted, so day lists like `"Mon, Wed"` stay in a single
+    /// column.
+    fn read_csv(
+        &mut self,
+        file_path: &Path,
+        sheet: Sheet,
+    ) -> Result<(), ReadError> {
+        let mut csv_reader = ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .trim(Trim::All)
+            .from_path(file_path)?;
+
+        for (i, record) in csv_reader.records().enumerate() {
+            let record = record?;
+            let row: Vec<String> = record.iter()
+                .map(|field| field.to_owned())
+                .collect();
+
+            self.read_row(sheet, i + 1, &row)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a single row of the input, regardless of the file it came from.
+    ///
+    /// `row_number` is 1-based and only used for error reporting.
+    fn read_row(
+        &mut self,
+        sheet: Sheet,
+        row_number: usize,
+        row: &[String],
+    ) -> Result<(), ReadError> {
+        if row.iter().all(|cell| cell.trim().is_empty()) {
+            return Ok(());
+        }
+
+        match sheet {
+            Sheet::Elements => self.read_element_row(row_number, row),
+
+            Sheet::Holidays => {
+                self.read_holiday_row(row_number, row);
+                Ok(())
+            }
+
+            Sheet::Ledger => {
+                self.read_ledger_row(row_number, row);
+                Ok(())
+            }
+
+            Sheet::Shifts => {
+                self.read_shift_row(row_number, row);
+                Ok(())
+            }
+
+            Sheet::Leave => {
+                self.read_leave_row(row_number, row);
+                Ok(())
+            }
+
+            Sheet::Coverage => {
+                self.read_coverage_row(row_number, row);
+                Ok(())
+            }
+
+            Sheet::Pairs => {
+                self.read_pair_row(row_number, row);
+                Ok(())
+            }
+        }
+    }
+
+    /// Reads a row of the "Pairs" sheet, e.g. "Ana", "Ben", "Together".
+    fn read_pair_row(&mut self, row_number: usize, row: &[String]) {
+        if let Some(columns) = PairColumns::from_header_row(row) {
+            self.pair_columns = columns;
+            return;
+        }
+
+        let cell = |i: usize| row.get(i)
+            .map(|c| c.trim())
+            .unwrap_or_default();
+
+        let first = cell(self.pair_columns.first);
+        let second = cell(self.pair_columns.second);
+
+        if first.is_empty() || second.is_empty() {
+            return;
+        }
+
+        let value = cell(self.pair_columns.rule);
+
+        match PairRule::from_name(value) {
+            Some(rule) => self.pairings.push(Pairing {
+                row: row_number,
+                ..Pairing::new(first, second, rule)
+            }),
+
+            None => self.row_errors.push(ReadError::BadPairRule {
+                row: row_number,
+                column: self.pair_columns.rule + 1,
+                value: value.to_owned(),
+            }),
+        }
+    }
+
+    /// Reads a row of the "Coverage" sheet, e.g. "Senior", "1", "Mon-Fri",
+    /// "Night".
+    fn read_coverage_row(&mut self, row_number: usize, row: &[String]) {
+        if let Some(columns) = CoverageColumns::from_header_row(row) {
+            self.coverage_columns = columns;
+            return;
+        }
+
+        let cell = |i: usize| row.get(i)
+            .map(|c| c.trim())
+            .unwrap_or_default();
+
+        let role = cell(self.coverage_columns.role);
+
+        if role.is_empty() {
+            return;
+        }
+
+        let min_column = self.coverage_columns.min;
+
+        let Some(min) = parse_count(cell(min_column)) else {
+            self.row_errors.push(ReadError::BadNumber {
+                row: row_number,
+                column: min_column + 1,
+                value: cell(min_column).to_owned(),
+            });
+
+            return;
+        };
+
+        let mut coverage = Coverage::new(role, min);
+
+        if let Some(i) = self.coverage_columns.days {
+            coverage.days = parse_days(
+                &self.day_names, cell(i), row_number, i, &mut self.row_errors,
+            );
+            self.day_lists.push((
+                DayList::Coverage(self.coverage.len()), cell(i).to_owned(),
+            ));
+        }
+
+        coverage.shift = self.coverage_columns.shift
+            .map(cell)
+            .filter(|shift| !shift.is_empty())
+            .map(|shift| shift.to_owned());
+
+        self.coverage.push(coverage);
+    }
+
+    /// Reads a row of the "Leave" sheet.
+    ///
+    /// The dates can be a list of dates and ranges, like in the "Leave"
+    /// column of the elements.
+    fn read_leave_row(&mut self, row_number: usize, row: &[String]) {
+        if let Some(columns) = LeaveColumns::from_header_row(row) {
+            self.leave_columns = columns;
+            return;
+        }
+
+        let cell = |i: usize| row.get(i)
+            .map(|c| c.trim())
+            .unwrap_or_default();
+
+        let name = cell(self.leave_columns.name);
+
+        if name.is_empty() {
+            return;
+        }
+
+        let dates = parse_date_ranges(
+            cell(self.leave_columns.dates),
+            row_number,
+            self.leave_columns.dates,
+            &mut self.row_errors,
+        );
+
+        self.leave.extend(dates.into_iter().map(|dates| Leave {
+            name: name.to_owned(),
+            row: row_number,
+            dates,
+        }));
+    }
+
+    /// Reads a row of the "Shifts" sheet.
+    fn read_shift_row(&mut self, row_number: usize, row: &[String]) {
+        if let Some(columns) = ShiftColumns::from_header_row(row) {
+            self.shift_columns = columns;
+            return;
+        }
+
+        let cell = |i: usize| row.get(i)
+            .map(|c| c.trim())
+            .unwrap_or_default();
+
+        let name = cell(self.shift_columns.name);
+
+        if name.is_empty() {
+            return;
+        }
+
+        let mut row_errors = Vec::new();
+
+        let mut time = |column: usize| {
+            let value = cell(column);
+            let time = parse_time(value);
+
+            if time.is_none() {
+                row_errors.push(ReadError::BadTime {
+                    row: row_number,
+                    column: column + 1,
+                    value: value.to_owned(),
+                });
+            }
+
+            time
+        };
+
+        let start = time(self.shift_columns.start);
+        let end = time(self.shift_columns.end);
+
+        // An empty headcount falls back to the headcount of the day
+        let headcount = self.shift_columns.headcount
+            .map(|column| (column, cell(column)))
+            .filter(|(_, value)| !value.is_empty())
+            .and_then(|(column, value)| {
+                let headcount = parse_count(value);
+
+                if headcount.is_none() {
+                    row_errors.push(ReadError::BadNumber {
+                        row: row_number,
+                        column: column + 1,
+                        value: value.to_owned(),
+                    });
+                }
+
+                headcount
+            });
+
+        if let (Some(start), Some(end)) = (start, end) {
+            self.shifts.push(Shift::new(name, start, end, headcount));
+        }
+
+        self.row_errors.extend(row_errors);
+    }
+
+    /// Reads a row of the "Ledger" sheet.
+    fn read_ledger_row(&mut self, row_number: usize, row: &[String]) {
+        if let Some(columns) = LedgerColumns::from_header_row(row) {
+            self.ledger_columns = columns;
+            return;
+        }
+
+        let cell = |i: usize| row.get(i)
+            .map(|c| c.trim())
+            .unwrap_or_default();
+
+        let name = cell(self.ledger_columns.name);
+
+        if name.is_empty() {
+            return;
+        }
+
+        // Excel numbers can come as "3" or "3.0"
+        let mut number = |column: usize| {
+            let value = cell(column);
+
+            if value.is_empty() {
+                return None;
+            }
+
+            let number = value.parse::<f64>().ok()
+                .filter(|n| n.is_finite());
+
+            if number.is_none() {
+                self.row_errors.push(ReadError::BadNumber {
+                    row: row_number,
+                    column: column + 1,
+                    value: value.to_owned(),
+                });
+            }
+
+            number
+        };
+
+        let debt = number(self.ledger_columns.debt)
+            .map(|debt| debt.round() as i32)
+            .unwrap_or_default();
+
+        let rotation = self.ledger_columns.rotation
+            .and_then(&mut number)
+            .filter(|rotation| *rotation >= 0.0)
+            .map(|rotation| rotation.round() as usize);
+
+        self.ledger.entries.push(LedgerEntry {
+            name: name.to_owned(),
+            debt,
+            rotation,
+        });
+    }
+
+    /// Reads a row of the "Holidays" sheet.
+    fn read_holiday_row(&mut self, row_number: usize, row: &[String]) {
+        if let Some(columns) = HolidayColumns::from_header_row(row) {
+            self.holiday_columns = columns;
+            return;
+        }
+
+        let cell = |i: usize| row.get(i)
+            .map(|c| c.trim())
+            .unwrap_or_default();
+
+        let value = cell(self.holiday_columns.date);
+
+        if value.is_empty() {
+            return;
+        }
+
+        match parse_date(value) {
+            Some(date) => {
+                let name = self.holiday_columns.name
+                    .map(cell)
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or("Holiday");
+
+                self.holidays.push(Holiday::new(date, name));
+            }
+
+            None => self.row_errors.push(ReadError::BadDate {
+                row: row_number,
+                column: self.holiday_columns.date + 1,
+                value: value.to_owned(),
+            }),
+        }
+    }
+
+    /// Reads a row of the elements sheet.
+    fn read_element_row(
+        &mut self,
+        row_number: usize,
+        row: &[String],
+    ) -> Result<(), ReadError> {
+        // If it's the header row, use it to find the columns and ignore this
+        // row. It has to come before the elements, so that an element named
+        // e.g. "Member" isn't taken for one.
+        if !self.elements_started {
+            if let Some(columns) = ColumnMap::from_header_row(row)? {
+                self.columns = columns;
+                return Ok(());
+            }
+        }
+
+        let mut element = ScheduledElement {
+            row: row_number,
+            ..ScheduledElement::default()
+        };
+        let cell = |i: usize| row.get(i)
+            .map(|c| c.trim())
+            .unwrap_or_default();
+
+        // Unknown days are skipped and kept in `row_errors` so that every bad
+        // day in the file can be reported at once.
+        let mut row_errors = Vec::new();
+
+        let mut day_lists = Vec::new();
+        let id = self.elements.len();
+
+        let parse_names = |data: &str| data.split(",")
+            .map(|name| name.trim().to_owned())
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<String>>();
+
+        let mut date_errors = Vec::new();
+        let mut number_errors = Vec::new();
+
+        let mut parse_limit = |i: usize, data: &str| {
+            let count = parse_count(data);
+
+            if count.is_none() {
+                number_errors.push(ReadError::BadNumber {
+                    row: row_number,
+                    column: i + 1,
+                    value: data.to_owned(),
+                });
+            }
+
+            count
+        };
+
+        // Actual data insertion takes place here:
+        for (i, column) in self.columns.known.iter() {
+            let data = cell(*i);
+
+            if data.is_empty() {
+                continue;
+            }
+
+            match column {
+                Column::Name => {
+                    element.text = data.to_owned();
+                }
+
+                Column::PreferDays => {
+                    element.prefer_days = parse_days(
+                        &self.day_names, data, row_number, *i, &mut row_errors,
+                    );
+                    day_lists.push((DayList::PreferDays(id), data.to_owned()));
+                }
+
+                Column::AvoidDays => {
+                    element.avoid_days = parse_days(
+                        &self.day_names, data, row_number, *i, &mut row_errors,
+                    );
+                    day_lists.push((DayList::AvoidDays(id), data.to_owned()));
+                }
+
+                Column::PreferShifts => {
+                    element.prefer_shifts = parse_names(data);
+                }
+
+                Column::AvoidShifts => {
+                    element.avoid_shifts = parse_names(data);
+                }
+
+                Column::Leave => {
+                    element.leave = parse_date_ranges(
+                        data, row_number, *i, &mut date_errors,
+                    );
+                }
+
+                Column::MaxAssignments => {
+                    element.max_assignments = parse_limit(*i, data);
+                }
+
+                Column::MinAssignments => {
+                    element.min_assignments = parse_limit(*i, data);
+                }
+
+                Column::Roles => {
+                    element.roles = parse_names(data);
+                }
+            }
+        }
+
+        for (i, header) in self.columns.extra.iter() {
+            let data = cell(*i);
+
+            if !data.is_empty() {
+                element.metadata.push((header.clone(), data.to_owned()));
+            }
+        }
+
+        self.elements.push(element);
+        self.elements_started = true;
+        self.day_lists.extend(day_lists);
+        self.row_errors.extend(row_errors);
+        self.row_errors.extend(date_errors);
+        self.row_errors.extend(number_errors);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+//! The schedule generated for a month.
+
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::{
+    calendar::Calendar, ledger::Ledger, reader::ScheduledElement,
+    shift::Shift, trace::{ Reason, Trace },
+};
+
+/// An element scheduled on a date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    pub date: NaiveDate,
+
+    /// Index of the element in [`Schedule::elements`]
+    pub element_id: usize,
+
+    /// Index of the shift in [`Schedule::shifts`]
+    pub shift: usize,
+}
+
+/// A rule the schedule couldn't follow.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduleWarning {
+    /// The element is scheduled on fewer dates than its
+    /// [`ScheduledElement::min_assignments`].
+    BelowMinimum {
+        element_id: usize,
+        name: String,
+        assigned: usize,
+        min: usize,
+    },
+
+    /// Fewer elements with the role are scheduled on the shift than its
+    /// [`Coverage`](crate::coverage::Coverage) needs.
+    RoleUncovered {
+        date: NaiveDate,
+
+        /// The name of the shift, `None` if the day is a single shift
+        shift: Option<String>,
+        role: String,
+        covered: usize,
+        min: usize,
+    },
+
+    /// Fewer elements are scheduled on the shift than its headcount.
+    Underfilled {
+        date: NaiveDate,
+
+        /// The name of the shift, `None` if the day is a single shift
+        shift: Option<String>,
+        scheduled: usize,
+        headcount: usize,
+
+        /// The number of the other elements kept off the shift by each rule
+        reasons: Vec<(Reason, usize)>,
+    },
+}
+
+impl fmt::Display for ScheduleWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BelowMinimum { name, assigned, min, .. } => write!(
+                f,
+                "\"{}\" is scheduled on {} days, fewer than the minimum of {}",
+                name,
+                assigned,
+                min,
+            ),
+
+            Self::RoleUncovered { date, shift, role, covered, min } => {
+                write!(
+                    f,
+                    "\"{}\" is covered by {} of the {} needed on {}",
+                    role,
+                    covered,
+                    min,
+                    date.format("%a %Y-%m-%d"),
+                )?;
+
+                match shift {
+                    Some(shift) => write!(f, " ({})", shift),
+                    None => Ok(()),
+                }
+            }
+
+            Self::Underfilled { date, shift, scheduled, headcount, reasons } => {
+                write!(f, "{}", date.format("%a %Y-%m-%d"))?;
+
+                if let Some(shift) = shift {
+                    write!(f, " ({})", shift)?;
+                }
+
+                write!(
+                    f,
+                    " is short by {}, {} of the {} needed are scheduled",
+                    headcount - scheduled,
+                    scheduled,
+                    headcount,
+                )?;
+
+                for (i, (reason, count)) in reasons.iter().enumerate() {
+                    let separator = if i == 0 { " (" } else { ", " };
+                    write!(f, "{}{}: {}", separator, reason, count)?;
+                }
+
+                if reasons.is_empty() {
+                    Ok(())
+                } else {
+                    write!(f, ")")
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Schedule {
+    /// The month that's been scheduled
+    pub calendar: Calendar,
+
+    /// The elements that could be scheduled, in the order they were read
+    pub elements: Vec<ScheduledElement>,
+
+    /// The shifts of each day, like [`Calendar::shifts`]
+    pub shifts: Vec<Shift>,
+
+    /// The assignments, ordered by their date
+    pub assignments: Vec<Assignment>,
+
+    /// The seed the elements were shuffled with, to generate the same
+    /// schedule again
+    pub seed: u64,
+
+    /// The debts and rotation at the end of the month, to be carried over to
+    /// the next one
+    pub ledger: Ledger,
+
+    /// The rules that couldn't be followed, e.g. minimum assignments that
+    /// weren't met
+    pub warnings: Vec<ScheduleWarning>,
+
+    /// The decisions of the rotation, if [`Rules::trace`](crate::worker::Rules::trace)
+    /// was set
+    pub trace: Trace,
+}
+
+impl Schedule {
+    pub fn new(
+        calendar: &Calendar,
+        elements: &[ScheduledElement],
+        seed: u64,
+    ) -> Self {
+        Self {
+            calendar: calendar.clone(),
+            elements: elements.to_vec(),
+            shifts: calendar.shifts.clone(),
+            assignments: Vec::new(),
+            seed,
+            ledger: Ledger::default(),
+            warnings: Vec::new(),
+            trace: Trace::default(),
+        }
+    }
+
+    /// Schedules the element on the date.
+    pub fn assign(&mut self, date: NaiveDate, element_id: usize, shift: usize) {
+        self.assignments.push(Assignment { date, element_id, shift });
+    }
+
+    /// Gets the element of the assignment.
+    pub fn element(&self, assignment: &Assignment) -> &ScheduledElement {
+        &self.elements[assignment.element_id]
+    }
+
+    /// Gets the assignments on the date.
+    pub fn on_date(&self, date: NaiveDate) -> impl Iterator<Item = &Assignment> {
+        self.assignments.iter().filter(move |a| a.date == date)
+    }
+
+    /// Gets the names of the elements scheduled on the date.
+    pub fn names_on(&self, date: NaiveDate) -> Vec<&str> {
+        self.on_date(date)
+            .map(|a| self.element(a).text.as_str())
+            .collect()
+    }
+
+    /// Gets the names of the elements scheduled on the shift of the date.
+    pub fn names_in(&self, date: NaiveDate, shift: usize) -> Vec<&str> {
+        self.on_date(date)
+            .filter(|a| a.shift == shift)
+            .map(|a| self.element(a).text.as_str())
+            .collect()
+    }
+
+    /// Iterates over every date of the month, along with its assignments.
+    pub fn by_date(
+        &self,
+    ) -> impl Iterator<Item = (NaiveDate, Vec<&Assignment>)> + '_ {
+        (1..=self.calendar.num_days())
+            .filter_map(|day| self.calendar.date(day))
+            .map(|date| (date, self.on_date(date).collect()))
+    }
+
+    /// Iterates over the weeks of the month, each with its assignments.
+    ///
+    /// Weeks are laid out like [`Calendar::weeks`].
+    pub fn by_week(&self) -> impl Iterator<Item = Vec<&Assignment>> + '_ {
+        self.calendar.weeks().into_iter()
+            .map(|week| self.assignments.iter()
+                .filter(|a| week.contains(&Some(a.date)))
+                .collect())
+    }
+
+    /// Gets the assignments of the element.
+    pub fn by_element(
+        &self,
+        element_id: usize,
+    ) -> impl Iterator<Item = &Assignment> {
+        self.assignments.iter().filter(move |a| a.element_id == element_id)
+    }
+}
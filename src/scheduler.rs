@@ -0,0 +1,344 @@
+//! The ways the elements of a month can be scheduled.
+//!
+//! Each way is a [`Scheduler`]. The ones that come with the crate are in the
+//! default [`Registry`], and other ones can be registered along with them,
+//! e.g. with [`Window::with_schedulers`](crate::ui::Window::with_schedulers).
+//! A [`Draft`] keeps to the hard rules while a scheduler fills the shifts.
+
+use chrono::{ Datelike, NaiveDate };
+use rand::{ rng, Rng };
+
+use crate::{
+    calendar::Calendar, coverage::Coverage, ledger::{ Ledger, LedgerEntry },
+    pairing::PairRule, reader::ScheduledElement,
+    schedule::{ Schedule, ScheduleWarning },
+    solver::Optimal,
+    strategies::{ RandomDraw, RoundRobin, Weighted },
+    types::Day, worker::{ self, Rotation, Rules },
+};
+
+/// Schedules elements on the dates of a calendar.
+pub trait Scheduler {
+    /// The name of the scheduler, as it's shown in the UI
+    fn name(&self) -> &str;
+
+    /// Schedules the elements for the month.
+    ///
+    /// Params:
+    /// - `elements` The elements to schedule
+    /// - `calendar` The month of schedule, with its shifts and headcounts
+    /// - `rules` The rules for this run, e.g. the seed and the rest rules
+    ///
+    /// Returns the schedule with the elements assigned to each scheduled date.
+    fn schedule(
+        &self,
+        elements: &[ScheduledElement],
+        calendar: &Calendar,
+        rules: &Rules,
+    ) -> Schedule;
+}
+
+/// The schedulers that can be picked, by their name.
+pub struct Registry {
+    schedulers: Vec<Box<dyn Scheduler>>,
+}
+
+impl Default for Registry {
+    /// The schedulers that come with the crate, the rotation first.
+    fn default() -> Self {
+        let mut registry = Self::new();
+
+        registry.register(Rotation);
+        registry.register(Optimal::default());
+        registry.register(RoundRobin);
+        registry.register(RandomDraw);
+        registry.register(Weighted);
+
+        registry
+    }
+}
+
+impl Registry {
+    /// Creates a registry without any schedulers.
+    pub fn new() -> Self {
+        Self { schedulers: Vec::new() }
+    }
+
+    /// Adds the scheduler, in place of the one with the same name if there's
+    /// one.
+    pub fn register(&mut self, scheduler: impl Scheduler + 'static) {
+        let scheduler: Box<dyn Scheduler> = Box::new(scheduler);
+
+        match self.schedulers.iter()
+            .position(|other| other.name() == scheduler.name())
+        {
+            Some(index) => self.schedulers[index] = scheduler,
+            None => self.schedulers.push(scheduler),
+        }
+    }
+
+    /// Gets the scheduler with the name.
+    pub fn get(&self, name: &str) -> Option<&dyn Scheduler> {
+        self.schedulers.iter()
+            .find(|scheduler| scheduler.name() == name)
+            .map(|scheduler| scheduler.as_ref())
+    }
+
+    /// Gets the scheduler registered first, if there's any.
+    pub fn first(&self) -> Option<&dyn Scheduler> {
+        self.schedulers.first().map(|scheduler| scheduler.as_ref())
+    }
+
+    /// Gets the names of the schedulers, in the order they were registered.
+    pub fn names(&self) -> Vec<String> {
+        self.schedulers.iter()
+            .map(|scheduler| scheduler.name().to_owned())
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schedulers.is_empty()
+    }
+}
+
+/// A schedule being filled by a [`Scheduler`], which keeps to the hard rules:
+/// the days and shifts each element avoids, its leave and its most
+/// assignments, a single shift each date, the rest rules and the pairs.
+///
+/// Elements that are always scheduled together are assigned as a group, see
+/// [`Draft::group`].
+pub struct Draft<'a> {
+    elements: &'a [ScheduledElement],
+    calendar: &'a Calendar,
+    rules: &'a Rules,
+    schedule: Schedule,
+
+    /// The dates and shifts each element is scheduled on, in order
+    worked: Vec<Vec<(NaiveDate, usize)>>,
+
+    /// The other elements of the pairs each element is in, with their rule
+    partners: Vec<Vec<(usize, PairRule)>>,
+}
+
+impl<'a> Draft<'a> {
+    /// Starts an empty schedule, with the seed of the rules or a random one.
+    pub fn new(
+        elements: &'a [ScheduledElement],
+        calendar: &'a Calendar,
+        rules: &'a Rules,
+    ) -> Self {
+        let seed = rules.seed.unwrap_or_else(|| rng().random());
+
+        Self {
+            elements,
+            calendar,
+            rules,
+            schedule: Schedule::new(calendar, elements, seed),
+            worked: vec![Vec::new(); elements.len()],
+            partners: worker::partners(elements, &rules.pairings),
+        }
+    }
+
+    /// The seed to shuffle or draw the elements with.
+    pub fn seed(&self) -> u64 {
+        self.schedule.seed
+    }
+
+    pub fn elements(&self) -> &'a [ScheduledElement] {
+        self.elements
+    }
+
+    pub fn rules(&self) -> &'a Rules {
+        self.rules
+    }
+
+    /// Gets the shifts to fill in the order of their dates, as the date, the
+    /// index of the shift and its headcount.
+    ///
+    /// Days off, holidays and shifts without a headcount are left out.
+    pub fn slots(&self) -> Vec<(NaiveDate, usize, usize)> {
+        let mut slots = Vec::new();
+
+        for date in self.calendar.dates() {
+            let day = Day::from_u32(date.weekday().num_days_from_sunday());
+
+            if !self.calendar.is_working_day(day)
+                || self.calendar.holiday(date).is_some()
+            {
+                continue;
+            }
+
+            for (index, shift) in self.calendar.shifts.iter().enumerate() {
+                let headcount = self.calendar.shift_headcount(day, shift);
+
+                if headcount > 0 {
+                    slots.push((date, index, headcount));
+                }
+            }
+        }
+
+        slots
+    }
+
+    /// The number of dates the element is scheduled on so far.
+    pub fn count(&self, id: usize) -> usize {
+        self.worked[id].len()
+    }
+
+    /// Gets the elements scheduled on the shift of the date.
+    pub fn scheduled(&self, date: NaiveDate, shift: usize) -> Vec<usize> {
+        self.schedule.on_date(date)
+            .filter(|a| a.shift == shift)
+            .map(|a| a.element_id)
+            .collect()
+    }
+
+    /// Gets the coverage rules of the shift of the date that aren't met yet.
+    pub fn needed_roles(&self, date: NaiveDate, shift: usize) -> Vec<&'a Coverage> {
+        let day = Day::from_u32(date.weekday().num_days_from_sunday());
+        let scheduled = self.scheduled(date, shift);
+
+        self.calendar.coverage.iter()
+            .filter(|coverage| {
+                coverage.applies_to(day, &self.calendar.shifts[shift])
+                    && scheduled.iter()
+                        .filter(|id| self.elements[**id].has_role(&coverage.role))
+                        .count() < coverage.min
+            })
+            .collect()
+    }
+
+    /// Gets the element along with the elements that are always scheduled
+    /// together with it.
+    pub fn group(&self, id: usize) -> Vec<usize> {
+        let mut group = vec![id];
+        let mut index = 0;
+
+        while index < group.len() {
+            for (partner, rule) in self.partners[group[index]].iter() {
+                if *rule == PairRule::Together && !group.contains(partner) {
+                    group.push(*partner);
+                }
+            }
+
+            index += 1;
+        }
+
+        group
+    }
+
+    /// Whether the element, along with its [`Draft::group`], can be scheduled
+    /// on the shift of the date without breaking a hard rule.
+    pub fn can_assign(&self, id: usize, date: NaiveDate, shift: usize) -> bool {
+        let group = self.group(id);
+
+        group.iter().all(|member| {
+            let elem = &self.elements[*member];
+            let worked = &self.worked[*member];
+
+            !worked.iter().any(|(other, _)| *other == date)
+                && !worker::avoids(elem, date, &self.calendar.shifts[shift])
+                && elem.max_assignments.is_none_or(|max| worked.len() < max)
+                && !self.partners[*member].iter().any(|(partner, rule)| {
+                    *rule == PairRule::Apart
+                        && !group.contains(partner)
+                        && self.worked[*partner].iter()
+                            .any(|(other, _)| *other == date)
+                })
+                && worker::rests_enough(
+                    worked.iter()
+                        .copied()
+                        .filter(|(other, _)| *other < date)
+                        .chain(std::iter::once((date, shift)))
+                        .chain(worked.iter()
+                            .copied()
+                            .filter(|(other, _)| *other > date)),
+                    self.calendar,
+                    self.rules,
+                )
+        })
+    }
+
+    /// Schedules the element, along with its [`Draft::group`], on the shift
+    /// of the date. It should be checked with [`Draft::can_assign`] first.
+    ///
+    /// Returns the number of elements scheduled.
+    pub fn assign(&mut self, id: usize, date: NaiveDate, shift: usize) -> usize {
+        let group = self.group(id);
+
+        for member in group.iter() {
+            self.schedule.assign(date, *member, shift);
+
+            let worked = &mut self.worked[*member];
+            let at = worked.partition_point(|(other, _)| *other < date);
+            worked.insert(at, (date, shift));
+        }
+
+        group.len()
+    }
+
+    /// Finishes the schedule, with the warnings for the roles, minimums and
+    /// headcounts that weren't met.
+    ///
+    /// The debts in the ledger are the days each element is behind the
+    /// average, after the debts carried over. There's no rotation to carry
+    /// over.
+    pub fn finish(mut self) -> Schedule {
+        self.schedule.assignments.sort_by_key(|a| (a.date, a.shift));
+
+        for (date, shift, _) in self.slots() {
+            let scheduled = self.scheduled(date, shift);
+
+            for coverage in self.needed_roles(date, shift) {
+                self.schedule.warnings.push(ScheduleWarning::RoleUncovered {
+                    date,
+                    shift: (self.calendar.shifts.len() > 1)
+                        .then(|| self.calendar.shifts[shift].name.clone()),
+                    role: coverage.role.clone(),
+                    covered: scheduled.iter()
+                        .filter(|id| self.elements[**id].has_role(&coverage.role))
+                        .count(),
+                    min: coverage.min,
+                });
+            }
+        }
+
+        let adjusted: Vec<f64> = self.elements.iter()
+            .enumerate()
+            .map(|(id, elem)| {
+                self.count(id) as f64
+                    - self.rules.carry_over.debt(&elem.text) as f64
+            })
+            .collect();
+        let mean = adjusted.iter().sum::<f64>() / adjusted.len().max(1) as f64;
+
+        for (id, elem) in self.elements.iter().enumerate() {
+            let assigned = self.count(id);
+
+            if let Some(min) = elem.min_assignments.filter(|min| assigned < *min) {
+                self.schedule.warnings.push(ScheduleWarning::BelowMinimum {
+                    element_id: id,
+                    name: elem.text.clone(),
+                    assigned,
+                    min,
+                });
+            }
+        }
+
+        let gaps = worker::underfilled(&self.schedule, self.rules);
+        self.schedule.warnings.extend(gaps);
+
+        self.schedule.ledger = Ledger {
+            entries: self.elements.iter()
+                .zip(adjusted.iter())
+                .map(|(elem, adjusted)| LedgerEntry {
+                    name: elem.text.clone(),
+                    debt: (mean - adjusted).round() as i32,
+                    rotation: None,
+                })
+                .collect(),
+        };
+
+        self.schedule
+    }
+}
@@ -0,0 +1,85 @@
+//! The shifts each working day is split into.
+
+use std::fmt;
+
+use chrono::{ NaiveTime, TimeDelta };
+
+/// The name of the only shift of each day, if no shifts are defined
+pub const DEFAULT_SHIFT: &str = "All day";
+
+/// A part of the working day with its own elements, e.g. "Morning".
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shift {
+    pub name: String,
+    pub start: NaiveTime,
+
+    /// The time the shift ends, on the next day if it's not after `start`
+    pub end: NaiveTime,
+
+    /// The number of elements to schedule on the shift. If it's `None`, the
+    /// headcount of the day of the week is used.
+    pub headcount: Option<usize>,
+}
+
+impl Default for Shift {
+    /// The whole day as a single shift.
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_SHIFT.to_owned(),
+            start: NaiveTime::MIN,
+            end: NaiveTime::MIN,
+            headcount: None,
+        }
+    }
+}
+
+impl Shift {
+    pub fn new(
+        name: &str,
+        start: NaiveTime,
+        end: NaiveTime,
+        headcount: Option<usize>,
+    ) -> Self {
+        Self { name: name.trim().to_owned(), start, end, headcount }
+    }
+
+    /// Whether the shift ends on the day after it starts, e.g. a night shift.
+    pub fn ends_next_day(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// Gets the length of the shift.
+    pub fn duration(&self) -> TimeDelta {
+        let duration = self.end - self.start;
+
+        if self.ends_next_day() {
+            duration + TimeDelta::days(1)
+        } else {
+            duration
+        }
+    }
+
+    /// Whether this is the whole day as a single shift, used when no shifts
+    /// are defined.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the `name` refers to this shift, ignoring the case.
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name.trim())
+    }
+}
+
+impl fmt::Display for Shift {
+    /// Writes the shift as e.g. "Morning (06:00-14:00)".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}-{})",
+            self.name,
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M"),
+        )
+    }
+}
@@ -0,0 +1,763 @@
+//! Scheduling by optimizing weighted objectives, as an alternative to the
+//! rotation.
+//!
+//! The rotation's schedule is the starting point. Its assignments that break a
+//! hard rule are dropped first, then it's improved with a local search
+//! (simulated annealing) that adds, moves and swaps elements between the
+//! shifts. The moves never break the hard rules:
+//! - the days and shifts an element avoids, and its leave
+//! - a single shift each date, and the most assignments of an element
+//! - the most days in a row and the rest between shifts
+//! - the pairs, the elements scheduled together are kept where the rotation
+//!   put them
+//!
+//! The rest are soft objectives, weighed against each other by [`Weights`].
+
+use std::fmt;
+
+use chrono::{ Datelike, NaiveDate };
+use rand::{ rngs::StdRng, Rng, SeedableRng };
+
+use crate::{
+    calendar::Calendar, coverage::Coverage, pairing::PairRule,
+    reader::ScheduledElement,
+    schedule::{ Assignment, Schedule, ScheduleWarning },
+    scheduler::Scheduler, trace::Trace, types::Day,
+    worker::{ self, Rotation, Rules },
+};
+
+/// The temperature of the search at its start, in points of the score
+const START_TEMPERATURE: f64 = 20.0;
+
+/// The temperature of the search at its end
+const END_TEMPERATURE: f64 = 0.05;
+
+/// How much each soft objective weighs in the [`Score`] of a schedule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Weights {
+    /// Each empty place on a shift
+    pub underfilled: f64,
+
+    /// Each element missing from the roles a shift needs
+    pub uncovered: f64,
+
+    /// Each day an element is short of its minimum
+    pub below_minimum: f64,
+
+    /// The spread of the days between the elements, see [`Score::unfairness`]
+    pub unfairness: f64,
+
+    /// Each preferred day or shift an element is scheduled on, taken off the
+    /// score. It's scaled by [`Rules::preference_weight`].
+    pub preferred: f64,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self {
+            underfilled: 1000.0,
+            uncovered: 500.0,
+            below_minimum: 200.0,
+            unfairness: 10.0,
+            preferred: 20.0,
+        }
+    }
+}
+
+/// How well a schedule meets the soft objectives, lower is better.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Score {
+    /// The empty places on the shifts
+    pub underfilled: usize,
+
+    /// The elements missing from the roles the shifts need
+    pub uncovered: usize,
+
+    /// The days the elements are short of their minimums
+    pub below_minimum: usize,
+
+    /// The sum of the squared differences between the days of each element
+    /// and the average, after the debts carried over
+    pub unfairness: f64,
+
+    /// The preferred days and shifts the elements are scheduled on
+    pub preferred: usize,
+
+    /// The weighted sum of the objectives
+    pub total: f64,
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Empty places: {}, missing roles: {}, days below minimums: {}, \
+            unfairness: {:.1}, preferred days: {}",
+            self.underfilled,
+            self.uncovered,
+            self.below_minimum,
+            self.unfairness,
+            self.preferred,
+        )
+    }
+}
+
+/// Schedules with the rotation, then improves the schedule against the
+/// [`Weights`].
+#[derive(Debug, Clone, Copy)]
+pub struct Optimal {
+    pub weights: Weights,
+
+    /// The number of moves the search tries
+    pub iterations: usize,
+}
+
+impl Default for Optimal {
+    fn default() -> Self {
+        Self { weights: Weights::default(), iterations: 20_000 }
+    }
+}
+
+impl Scheduler for Optimal {
+    fn name(&self) -> &str { "Optimal" }
+
+    fn schedule(
+        &self,
+        elements: &[ScheduledElement],
+        calendar: &Calendar,
+        rules: &Rules,
+    ) -> Schedule {
+        self.improve(Rotation.schedule(elements, calendar, rules), rules)
+    }
+}
+
+impl Optimal {
+    /// Improves a schedule made by another scheduler, e.g. the rotation.
+    ///
+    /// The assignments that break a hard rule are dropped before the search,
+    /// along with the ones of the elements scheduled together with them.
+    ///
+    /// Params:
+    /// - `schedule` The schedule to start from
+    /// - `rules` The rules it was made with
+    pub fn improve(&self, mut schedule: Schedule, rules: &Rules) -> Schedule {
+        let elements = schedule.elements.clone();
+        let calendar = schedule.calendar.clone();
+        let mut model = Model::new(
+            &elements, &calendar, &schedule.assignments, rules, &self.weights,
+        );
+
+        model.repair();
+        model.search(self.iterations, schedule.seed);
+        model.apply_to(&mut schedule);
+
+        schedule
+    }
+}
+
+/// Scores the schedule against the soft objectives.
+///
+/// Params:
+/// - `schedule` The schedule to score
+/// - `rules` The rules it was made with, for the preference weight and the
+///   debts carried over
+/// - `weights` How much each objective weighs
+pub fn evaluate(schedule: &Schedule, rules: &Rules, weights: &Weights) -> Score {
+    Model::new(
+        &schedule.elements, &schedule.calendar, &schedule.assignments, rules,
+        weights,
+    ).score()
+}
+
+/// A shift of a scheduled date.
+struct Place<'a> {
+    date: NaiveDate,
+    shift_index: usize,
+    headcount: usize,
+
+    /// The roles the shift needs
+    coverage: Vec<&'a Coverage>,
+}
+
+/// A change to the schedule the search tries.
+enum Move {
+    /// Schedules the element on an empty place
+    Add { place: usize, elem: usize },
+
+    /// Schedules `into` in place of `out`
+    Replace { place: usize, out: usize, into: usize },
+
+    /// Moves the element to an empty place of another shift
+    Transfer { from: usize, to: usize, elem: usize },
+
+    /// Swaps two elements of different shifts
+    Swap { first: usize, a: usize, second: usize, b: usize },
+}
+
+impl Move {
+    /// Gets the places and the elements the move changes.
+    fn touched(&self) -> (Vec<usize>, Vec<usize>) {
+        match *self {
+            Move::Add { place, elem } => (vec![place], vec![elem]),
+            Move::Replace { place, out, into } => (vec![place], vec![out, into]),
+            Move::Transfer { from, to, elem } => (vec![from, to], vec![elem]),
+            Move::Swap { first, a, second, b } => (vec![first, second], vec![a, b]),
+        }
+    }
+}
+
+/// The schedule as the search sees it.
+struct Model<'a> {
+    elements: &'a [ScheduledElement],
+    calendar: &'a Calendar,
+    rules: &'a Rules,
+    weights: &'a Weights,
+
+    /// The shifts of the scheduled dates, in the order of their dates
+    places: Vec<Place<'a>>,
+
+    /// The elements scheduled on each place
+    members: Vec<Vec<usize>>,
+
+    /// The places each element is scheduled on, in order
+    worked: Vec<Vec<usize>>,
+
+    /// Whether each element avoids each place, by place
+    avoids: Vec<Vec<bool>>,
+
+    /// Whether each element prefers each place, by place
+    prefers: Vec<Vec<bool>>,
+
+    /// The debt each element carried over from the previous period
+    debts: Vec<f64>,
+
+    /// The other elements of the pairs each element is in, with their rule
+    partners: Vec<Vec<(usize, PairRule)>>,
+
+    /// The elements the search can move, i.e. the ones not scheduled together
+    /// with another
+    movable: Vec<usize>,
+}
+
+impl<'a> Model<'a> {
+    fn new(
+        elements: &'a [ScheduledElement],
+        calendar: &'a Calendar,
+        assignments: &[Assignment],
+        rules: &'a Rules,
+        weights: &'a Weights,
+    ) -> Self {
+        let mut places = Vec::new();
+
+        for date in calendar.dates() {
+            let day = Day::from_u32(date.weekday().num_days_from_sunday());
+
+            if !calendar.is_working_day(day) || calendar.holiday(date).is_some() {
+                continue;
+            }
+
+            for (shift_index, shift) in calendar.shifts.iter().enumerate() {
+                places.push(Place {
+                    date,
+                    shift_index,
+                    headcount: calendar.shift_headcount(day, shift),
+                    coverage: calendar.coverage.iter()
+                        .filter(|coverage| coverage.applies_to(day, shift))
+                        .collect(),
+                });
+            }
+        }
+
+        let mut members = vec![Vec::new(); places.len()];
+        let mut worked = vec![Vec::new(); elements.len()];
+
+        for assignment in assignments.iter() {
+            if let Some(place) = places.iter().position(|place| {
+                place.date == assignment.date
+                    && place.shift_index == assignment.shift
+            }) {
+                members[place].push(assignment.element_id);
+                worked[assignment.element_id].push(place);
+            }
+        }
+
+        for places in worked.iter_mut() {
+            places.sort_unstable();
+        }
+
+        let avoids = places.iter()
+            .map(|place| elements.iter()
+                .map(|elem| worker::avoids(
+                    elem, place.date, &calendar.shifts[place.shift_index]
+                ))
+                .collect())
+            .collect();
+
+        let prefers = places.iter()
+            .map(|place| elements.iter()
+                .map(|elem| worker::prefers(
+                    elem, place.date, &calendar.shifts[place.shift_index]
+                ))
+                .collect())
+            .collect();
+
+        let partners = worker::partners(elements, &rules.pairings);
+        let movable = (0..elements.len())
+            .filter(|id| partners[*id].iter()
+                .all(|(_, rule)| *rule != PairRule::Together))
+            .collect();
+
+        Self {
+            elements,
+            calendar,
+            rules,
+            weights,
+            places,
+            members,
+            worked,
+            avoids,
+            prefers,
+            debts: elements.iter()
+                .map(|elem| rules.carry_over.debt(&elem.text) as f64)
+                .collect(),
+            partners,
+            movable,
+        }
+    }
+
+    /// The days of each element, less the debts carried over.
+    fn adjusted_counts(&self) -> Vec<f64> {
+        self.worked.iter()
+            .zip(self.debts.iter())
+            .map(|(places, debt)| places.len() as f64 - debt)
+            .collect()
+    }
+
+    /// The weighted cost of the empty places, the missing roles and the
+    /// preferred days of the place.
+    fn place_cost(&self, place: usize) -> f64 {
+        let members = &self.members[place];
+        let underfilled = self.places[place].headcount
+            .saturating_sub(members.len());
+        let uncovered: usize = self.places[place].coverage.iter()
+            .map(|coverage| coverage.min.saturating_sub(
+                members.iter()
+                    .filter(|id| self.elements[**id].has_role(&coverage.role))
+                    .count()
+            ))
+            .sum();
+        let preferred = members.iter()
+            .filter(|id| self.prefers[place][**id])
+            .count();
+
+        underfilled as f64 * self.weights.underfilled
+            + uncovered as f64 * self.weights.uncovered
+            - preferred as f64 * self.weights.preferred
+                * self.rules.preference_weight as f64
+    }
+
+    /// The weighted cost of the days the element is short of its minimum.
+    fn element_cost(&self, elem: usize) -> f64 {
+        let below_minimum = self.elements[elem].min_assignments
+            .map_or(0, |min| min.saturating_sub(self.worked[elem].len()));
+
+        below_minimum as f64 * self.weights.below_minimum
+    }
+
+    /// Gets the part of the score that depends on the places and elements, and
+    /// the sum of the adjusted days of the elements and of their squares.
+    fn local(&self, places: &[usize], elems: &[usize]) -> (f64, f64, f64) {
+        let cost = places.iter().map(|place| self.place_cost(*place)).sum::<f64>()
+            + elems.iter().map(|elem| self.element_cost(*elem)).sum::<f64>();
+        let adjusted = elems.iter()
+            .map(|elem| self.worked[*elem].len() as f64 - self.debts[*elem]);
+
+        (
+            cost,
+            adjusted.clone().sum(),
+            adjusted.map(|count| count * count).sum(),
+        )
+    }
+
+    /// The sum of the squared differences from the average, from the sum of
+    /// the adjusted days and of their squares.
+    fn unfairness(&self, sum: f64, sum_sq: f64) -> f64 {
+        let count = self.elements.len().max(1) as f64;
+
+        sum_sq - sum * sum / count
+    }
+
+    fn score(&self) -> Score {
+        let mut score = Score::default();
+
+        for (place, members) in self.places.iter().zip(self.members.iter()) {
+            score.underfilled += place.headcount.saturating_sub(members.len());
+
+            for coverage in place.coverage.iter() {
+                let covered = members.iter()
+                    .filter(|id| self.elements[**id].has_role(&coverage.role))
+                    .count();
+
+                score.uncovered += coverage.min.saturating_sub(covered);
+            }
+        }
+
+        for (place, members) in self.members.iter().enumerate() {
+            score.preferred += members.iter()
+                .filter(|id| self.prefers[place][**id])
+                .count();
+        }
+
+        for (elem, places) in self.elements.iter().zip(self.worked.iter()) {
+            score.below_minimum += elem.min_assignments
+                .map_or(0, |min| min.saturating_sub(places.len()));
+        }
+
+        let adjusted = self.adjusted_counts();
+
+        if !adjusted.is_empty() {
+            let mean = adjusted.iter().sum::<f64>() / adjusted.len() as f64;
+
+            score.unfairness = adjusted.iter()
+                .map(|count| (count - mean).powi(2))
+                .sum();
+        }
+
+        score.total = score.underfilled as f64 * self.weights.underfilled
+            + score.uncovered as f64 * self.weights.uncovered
+            + score.below_minimum as f64 * self.weights.below_minimum
+            + score.unfairness * self.weights.unfairness
+            - score.preferred as f64 * self.weights.preferred
+                * self.rules.preference_weight as f64;
+
+        score
+    }
+
+    /// Whether the element can be scheduled on the place `joining`, once it's
+    /// off the place it's `leaving`, without breaking a hard rule.
+    fn fits(&self, elem: usize, leaving: Option<usize>, joining: usize) -> bool {
+        let date = self.places[joining].date;
+        let kept: Vec<usize> = self.worked[elem].iter()
+            .copied()
+            .filter(|place| Some(*place) != leaving)
+            .collect();
+
+        if self.avoids[joining][elem]
+            || kept.iter().any(|place| self.places[*place].date == date)
+            || self.elements[elem].max_assignments
+                .is_some_and(|max| kept.len() >= max)
+        {
+            return false;
+        }
+
+        let apart = self.partners[elem].iter().any(|(partner, rule)| {
+            *rule == PairRule::Apart
+                && self.worked[*partner].iter()
+                    .any(|place| self.places[*place].date == date)
+        });
+
+        if apart {
+            return false;
+        }
+
+        let mut places = kept;
+        places.push(joining);
+        places.sort_unstable();
+
+        worker::rests_enough(
+            places.iter()
+                .map(|place| {
+                    (self.places[*place].date, self.places[*place].shift_index)
+                }),
+            self.calendar,
+            self.rules,
+        )
+    }
+
+    /// Drops the assignments that break a hard rule, going through the places
+    /// in the order of their dates. An element dropped from a place takes the
+    /// elements scheduled together with it along.
+    fn repair(&mut self) {
+        let members = std::mem::replace(
+            &mut self.members, vec![Vec::new(); self.places.len()]
+        );
+
+        for worked in self.worked.iter_mut() {
+            worked.clear();
+        }
+
+        for (place, members) in members.into_iter().enumerate() {
+            let mut dropped = Vec::new();
+
+            for elem in members.iter().copied() {
+                let partnered = self.partners[elem].iter().all(|(partner, rule)| {
+                    *rule == PairRule::Apart || members.contains(partner)
+                });
+
+                if partnered && self.fits(elem, None, place) {
+                    self.insert(place, elem);
+                } else {
+                    dropped.push(elem);
+                }
+            }
+
+            while let Some(elem) = dropped.pop() {
+                let together: Vec<usize> = self.partners[elem].iter()
+                    .filter(|(partner, rule)| *rule == PairRule::Together
+                        && self.members[place].contains(partner))
+                    .map(|(partner, _)| *partner)
+                    .collect();
+
+                for partner in together {
+                    self.remove(place, partner);
+                    dropped.push(partner);
+                }
+            }
+        }
+    }
+
+    /// Picks a move that keeps the hard rules, if the one tried does.
+    fn random_move(&self, rng: &mut StdRng) -> Option<Move> {
+        let place = rng.random_range(0..self.places.len());
+        let elem = self.movable[rng.random_range(0..self.movable.len())];
+        let movable_member = |rng: &mut StdRng, place: usize| {
+            let members: Vec<usize> = self.members[place].iter()
+                .copied()
+                .filter(|id| self.movable.contains(id))
+                .collect();
+
+            (!members.is_empty())
+                .then(|| members[rng.random_range(0..members.len())])
+        };
+
+        match rng.random_range(0..3) {
+            0 => {
+                if self.members[place].len() < self.places[place].headcount {
+                    self.fits(elem, None, place)
+                        .then_some(Move::Add { place, elem })
+                } else {
+                    let out = movable_member(rng, place)?;
+
+                    (out != elem && self.fits(elem, None, place))
+                        .then_some(Move::Replace { place, out, into: elem })
+                }
+            }
+
+            1 => {
+                let from = place;
+                let to = rng.random_range(0..self.places.len());
+                let elem = movable_member(rng, from)?;
+
+                (from != to
+                    && self.members[to].len() < self.places[to].headcount
+                    && self.fits(elem, Some(from), to))
+                    .then_some(Move::Transfer { from, to, elem })
+            }
+
+            _ => {
+                let first = place;
+                let second = rng.random_range(0..self.places.len());
+                let a = movable_member(rng, first)?;
+                let b = movable_member(rng, second)?;
+
+                (first != second
+                    && a != b
+                    && !self.members[second].contains(&a)
+                    && !self.members[first].contains(&b)
+                    && self.fits(a, Some(first), second)
+                    && self.fits(b, Some(second), first))
+                    .then_some(Move::Swap { first, a, second, b })
+            }
+        }
+    }
+
+    fn insert(&mut self, place: usize, elem: usize) {
+        self.members[place].push(elem);
+
+        let at = self.worked[elem].partition_point(|p| *p < place);
+        self.worked[elem].insert(at, place);
+    }
+
+    fn remove(&mut self, place: usize, elem: usize) {
+        self.members[place].retain(|id| *id != elem);
+        self.worked[elem].retain(|p| *p != place);
+    }
+
+    fn apply(&mut self, step: &Move) {
+        match *step {
+            Move::Add { place, elem } => self.insert(place, elem),
+
+            Move::Replace { place, out, into } => {
+                self.remove(place, out);
+                self.insert(place, into);
+            }
+
+            Move::Transfer { from, to, elem } => {
+                self.remove(from, elem);
+                self.insert(to, elem);
+            }
+
+            Move::Swap { first, a, second, b } => {
+                self.remove(first, a);
+                self.remove(second, b);
+                self.insert(second, a);
+                self.insert(first, b);
+            }
+        }
+    }
+
+    fn undo(&mut self, step: &Move) {
+        match *step {
+            Move::Add { place, elem } => self.remove(place, elem),
+
+            Move::Replace { place, out, into } => {
+                self.remove(place, into);
+                self.insert(place, out);
+            }
+
+            Move::Transfer { from, to, elem } => {
+                self.remove(to, elem);
+                self.insert(from, elem);
+            }
+
+            Move::Swap { first, a, second, b } => {
+                self.remove(second, a);
+                self.remove(first, b);
+                self.insert(first, a);
+                self.insert(second, b);
+            }
+        }
+    }
+
+    /// Searches for a better schedule, keeping the best one found.
+    ///
+    /// Worse moves are taken now and then, less and less as the search cools
+    /// down, so it doesn't get stuck on the first schedule it can't improve
+    /// with a single move.
+    fn search(&mut self, iterations: usize, seed: u64) {
+        if self.places.is_empty() || self.movable.is_empty() {
+            return;
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut current = self.score().total;
+        let mut best = (current, self.members.clone(), self.worked.clone());
+
+        // The sums the unfairness is worked out from
+        let adjusted = self.adjusted_counts();
+        let mut sum: f64 = adjusted.iter().sum();
+        let mut sum_sq: f64 = adjusted.iter().map(|count| count * count).sum();
+
+        for step in 0..iterations {
+            let progress = step as f64 / iterations as f64;
+            let temperature = START_TEMPERATURE
+                * (END_TEMPERATURE / START_TEMPERATURE).powf(progress);
+
+            let Some(change) = self.random_move(&mut rng) else {
+                continue;
+            };
+
+            // Only the places and elements of the move are scored again
+            let (places, elems) = change.touched();
+            let (cost_before, sum_before, sq_before) = self.local(&places, &elems);
+
+            self.apply(&change);
+
+            let (cost_after, sum_after, sq_after) = self.local(&places, &elems);
+            let next_sum = sum - sum_before + sum_after;
+            let next_sum_sq = sum_sq - sq_before + sq_after;
+            let next = current - cost_before + cost_after
+                + self.weights.unfairness * (
+                    self.unfairness(next_sum, next_sum_sq)
+                        - self.unfairness(sum, sum_sq)
+                );
+
+            if next <= current
+                || rng.random::<f64>() < ((current - next) / temperature).exp()
+            {
+                current = next;
+                sum = next_sum;
+                sum_sq = next_sum_sq;
+
+                if current < best.0 {
+                    best = (current, self.members.clone(), self.worked.clone());
+                }
+            } else {
+                self.undo(&change);
+            }
+        }
+
+        (_, self.members, self.worked) = best;
+    }
+
+    /// Replaces the assignments, warnings and debts of the schedule with the
+    /// ones of the model, and drops the trace of the rotation.
+    ///
+    /// The debts in the ledger become the days each element is behind the
+    /// average, the rotation is kept.
+    fn apply_to(&self, schedule: &mut Schedule) {
+        schedule.assignments.clear();
+        schedule.warnings.clear();
+
+        // The rotation's decisions don't explain the improved schedule
+        schedule.trace = Trace::default();
+
+        for (place, members) in self.places.iter().zip(self.members.iter()) {
+            let mut members = members.clone();
+            members.sort_unstable();
+
+            for elem in members.iter() {
+                schedule.assignments.push(Assignment {
+                    date: place.date,
+                    element_id: *elem,
+                    shift: place.shift_index,
+                });
+            }
+
+            for coverage in place.coverage.iter() {
+                let covered = members.iter()
+                    .filter(|id| self.elements[**id].has_role(&coverage.role))
+                    .count();
+
+                if covered < coverage.min {
+                    schedule.warnings.push(ScheduleWarning::RoleUncovered {
+                        date: place.date,
+                        shift: (self.calendar.shifts.len() > 1).then(|| {
+                            self.calendar.shifts[place.shift_index].name.clone()
+                        }),
+                        role: coverage.role.clone(),
+                        covered,
+                        min: coverage.min,
+                    });
+                }
+            }
+        }
+
+        for (id, elem) in self.elements.iter().enumerate() {
+            let assigned = self.worked[id].len();
+
+            if let Some(min) = elem.min_assignments.filter(|min| assigned < *min) {
+                schedule.warnings.push(ScheduleWarning::BelowMinimum {
+                    element_id: id,
+                    name: elem.text.clone(),
+                    assigned,
+                    min,
+                });
+            }
+        }
+
+        let gaps = worker::underfilled(schedule, self.rules);
+        schedule.warnings.extend(gaps);
+
+        let adjusted = self.adjusted_counts();
+        let mean = adjusted.iter().sum::<f64>() / adjusted.len().max(1) as f64;
+
+        for entry in schedule.ledger.entries.iter_mut() {
+            if let Some(id) = self.elements.iter()
+                .position(|elem| elem.text == entry.name)
+            {
+                entry.debt = (mean - adjusted[id]).round() as i32;
+            }
+        }
+    }
+}
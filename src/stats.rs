@@ -0,0 +1,139 @@
+//! Statistics of a generated schedule, to check how fair it is and how well
+//! its shifts are filled.
+
+use chrono::{ Datelike, NaiveDate };
+
+use crate::{ schedule::Schedule, types::Day };
+
+/// The sheet of the export with the statistics
+pub const STATS_SHEET: &str = "Stats";
+
+/// The statistics of an element of the schedule.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ElementStats {
+    /// Index of the element in [`Schedule::elements`]
+    pub element_id: usize,
+    pub name: String,
+
+    /// The number of dates the element is scheduled on
+    pub total: usize,
+
+    /// The number of dates on each day of the week, indexed by
+    /// [`Day::to_u32`]
+    pub by_day: [usize; 7],
+
+    /// The dates on Saturdays and Sundays, when they're working days
+    pub weekend: usize,
+
+    /// The most dates in a row the element is scheduled on
+    pub longest_streak: usize,
+
+    /// The debt of the element at the end of the month, from the ledger
+    pub debt: i32,
+}
+
+/// The statistics of a schedule, per element and for the whole month.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ScheduleStats {
+    /// The statistics of each element, in the order they were read
+    pub elements: Vec<ElementStats>,
+
+    /// The average number of dates per element
+    pub mean: f64,
+
+    /// The standard deviation of the number of dates per element
+    pub std_dev: f64,
+
+    /// The shifts with fewer elements than their headcount
+    pub unmet_slots: usize,
+
+    /// The empty places on those shifts
+    pub missing_places: usize,
+}
+
+impl ScheduleStats {
+    pub fn new(schedule: &Schedule) -> Self {
+        let calendar = &schedule.calendar;
+
+        let elements: Vec<ElementStats> = schedule.elements.iter()
+            .enumerate()
+            .map(|(id, elem)| {
+                let mut dates: Vec<NaiveDate> = schedule.by_element(id)
+                    .map(|a| a.date)
+                    .collect();
+                dates.sort_unstable();
+                dates.dedup();
+
+                let mut stats = ElementStats {
+                    element_id: id,
+                    name: elem.text.clone(),
+                    total: dates.len(),
+                    debt: schedule.ledger.debt(&elem.text),
+                    ..ElementStats::default()
+                };
+
+                let mut streak = 0;
+                let mut previous: Option<NaiveDate> = None;
+
+                for date in dates.iter() {
+                    let day = Day::from_u32(date.weekday().num_days_from_sunday());
+
+                    stats.by_day[day.to_u32() as usize] += 1;
+
+                    if day.is_weekend() {
+                        stats.weekend += 1;
+                    }
+
+                    streak = match previous {
+                        Some(previous) if (*date - previous).num_days() == 1 => {
+                            streak + 1
+                        }
+
+                        _ => 1,
+                    };
+                    stats.longest_streak = stats.longest_streak.max(streak);
+                    previous = Some(*date);
+                }
+
+                stats
+            })
+            .collect();
+
+        let count = elements.len().max(1) as f64;
+        let mean = elements.iter().map(|e| e.total as f64).sum::<f64>() / count;
+        let variance = elements.iter()
+            .map(|e| (e.total as f64 - mean).powi(2))
+            .sum::<f64>() / count;
+
+        let mut unmet_slots = 0;
+        let mut missing_places = 0;
+
+        for date in calendar.dates() {
+            let day = Day::from_u32(date.weekday().num_days_from_sunday());
+
+            if !calendar.is_working_day(day) || calendar.holiday(date).is_some() {
+                continue;
+            }
+
+            for (index, shift) in schedule.shifts.iter().enumerate() {
+                let headcount = calendar.shift_headcount(day, shift);
+                let scheduled = schedule.on_date(date)
+                    .filter(|a| a.shift == index)
+                    .count();
+
+                if scheduled < headcount {
+                    unmet_slots += 1;
+                    missing_places += headcount - scheduled;
+                }
+            }
+        }
+
+        Self {
+            elements,
+            mean,
+            std_dev: variance.sqrt(),
+            unmet_slots,
+            missing_places,
+        }
+    }
+}
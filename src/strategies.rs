@@ -0,0 +1,176 @@
+//! Simpler schedulers than the rotation, to compare it with or to start a new
+//! one from.
+//!
+//! Each of them fills the shifts in the order of their dates with a
+//! [`Draft`], so they keep to the same hard rules as the rotation.
+
+use rand::{ rngs::StdRng, seq::SliceRandom, Rng, SeedableRng };
+
+use crate::{
+    calendar::Calendar, reader::ScheduledElement, schedule::Schedule,
+    scheduler::{ Draft, Scheduler }, worker::{ self, Rules },
+};
+
+/// The weight of a preferred day or shift in [`Weighted`], at the full
+/// preference weight
+const PREFERENCE_BONUS: f64 = 2.0;
+
+/// The weight of a role the shift still needs in [`Weighted`]
+const ROLE_BONUS: f64 = 100.0;
+
+/// Takes the elements in turn, in a shuffled order. The ones that can't be
+/// scheduled are skipped, without being owed anything for it.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RoundRobin;
+
+impl Scheduler for RoundRobin {
+    fn name(&self) -> &str { "Round-robin" }
+
+    fn schedule(
+        &self,
+        elements: &[ScheduledElement],
+        calendar: &Calendar,
+        rules: &Rules,
+    ) -> Schedule {
+        let mut draft = Draft::new(elements, calendar, rules);
+        let mut order: Vec<usize> = (0..elements.len()).collect();
+        order.shuffle(&mut StdRng::seed_from_u64(draft.seed()));
+
+        // The place in the order of the next element's turn
+        let mut next = 0;
+
+        for (date, shift, headcount) in draft.slots() {
+            let mut filled = 0;
+            let start = next;
+
+            for step in 0..order.len() {
+                let place = (start + step) % order.len();
+                let id = order[place];
+
+                if filled + draft.group(id).len() <= headcount
+                    && draft.can_assign(id, date, shift)
+                {
+                    filled += draft.assign(id, date, shift);
+                    next = (place + 1) % order.len();
+                }
+
+                if filled >= headcount {
+                    break;
+                }
+            }
+        }
+
+        draft.finish()
+    }
+}
+
+/// Draws the elements of each shift at random, from the ones scheduled on the
+/// fewest dates so far.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RandomDraw;
+
+impl Scheduler for RandomDraw {
+    fn name(&self) -> &str { "Random draw" }
+
+    fn schedule(
+        &self,
+        elements: &[ScheduledElement],
+        calendar: &Calendar,
+        rules: &Rules,
+    ) -> Schedule {
+        let mut draft = Draft::new(elements, calendar, rules);
+        let mut rng = StdRng::seed_from_u64(draft.seed());
+
+        for (date, shift, headcount) in draft.slots() {
+            let mut filled = 0;
+
+            loop {
+                let candidates: Vec<usize> = (0..elements.len())
+                    .filter(|id| filled + draft.group(*id).len() <= headcount
+                        && draft.can_assign(*id, date, shift))
+                    .collect();
+
+                let Some(fewest) = candidates.iter()
+                    .map(|id| draft.count(*id))
+                    .min()
+                else {
+                    break;
+                };
+
+                let pool: Vec<usize> = candidates.into_iter()
+                    .filter(|id| draft.count(*id) == fewest)
+                    .collect();
+                let id = pool[rng.random_range(0..pool.len())];
+
+                filled += draft.assign(id, date, shift);
+            }
+        }
+
+        draft.finish()
+    }
+}
+
+/// Picks the elements with the highest weight for each shift. The weight is
+/// the days an element is behind the others and owed from the previous period,
+/// with a bonus for its preferred days and shifts and for the roles the shift
+/// still needs. Ties go to the first in a shuffled order.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Weighted;
+
+impl Scheduler for Weighted {
+    fn name(&self) -> &str { "Weighted" }
+
+    fn schedule(
+        &self,
+        elements: &[ScheduledElement],
+        calendar: &Calendar,
+        rules: &Rules,
+    ) -> Schedule {
+        let mut draft = Draft::new(elements, calendar, rules);
+        let mut order: Vec<usize> = (0..elements.len()).collect();
+        order.shuffle(&mut StdRng::seed_from_u64(draft.seed()));
+
+        for (date, shift, headcount) in draft.slots() {
+            let mut filled = 0;
+
+            loop {
+                let needed = draft.needed_roles(date, shift);
+                let weight = |id: usize| {
+                    let elem = &elements[id];
+                    let mut weight = rules.carry_over.debt(&elem.text) as f64
+                        - draft.count(id) as f64;
+
+                    if worker::prefers(elem, date, &calendar.shifts[shift]) {
+                        weight += PREFERENCE_BONUS
+                            * rules.preference_weight as f64;
+                    }
+
+                    if needed.iter().any(|coverage| elem.has_role(&coverage.role)) {
+                        weight += ROLE_BONUS;
+                    }
+
+                    weight
+                };
+
+                let best = order.iter()
+                    .copied()
+                    .filter(|id| filled + draft.group(*id).len() <= headcount
+                        && draft.can_assign(*id, date, shift))
+                    .map(|id| (id, weight(id)))
+                    .fold(None, |best: Option<(usize, f64)>, (id, weight)| {
+                        match best {
+                            Some((_, most)) if most >= weight => best,
+                            _ => Some((id, weight)),
+                        }
+                    });
+
+                match best {
+                    Some((id, _)) => filled += draft.assign(id, date, shift),
+                    None => break,
+                }
+            }
+        }
+
+        draft.finish()
+    }
+}
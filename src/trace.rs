@@ -0,0 +1,155 @@
+//! The decisions the rotation makes on each shift, to explain why an element
+//! is or isn't scheduled on a date.
+
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+/// The sheet of the export with the trace
+pub const TRACE_SHEET: &str = "Trace";
+
+/// What the rotation did with an element on a shift.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Picked,
+
+    /// Passed over, e.g. for a day it avoids
+    Skipped,
+
+    /// Taken off the shift after it was picked
+    Removed,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Picked => "Picked",
+            Self::Skipped => "Skipped",
+            Self::Removed => "Removed",
+        })
+    }
+}
+
+/// Why the rotation did what it did with an element on a shift.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reason {
+    /// Its turn came in the rotation's window
+    Rotation,
+
+    /// No more elements could work the shift than it needs
+    Everyone,
+
+    /// It prefers the day or the shift, swapped in or out for it
+    Preferred,
+
+    /// It has a role the shift needs and was picked before the others, or its
+    /// turn went to one that has
+    Role,
+
+    /// It had the most debt when a place was empty
+    Debt,
+
+    /// It was the next one after the window that could take an empty place
+    FillIn,
+
+    /// It's scheduled together with another element, swapped in or out for it
+    Pair,
+
+    /// It took a place freed by the pairs
+    Refill,
+
+    /// It was below its minimum, given the place or giving it away
+    Minimum,
+
+    AvoidsDay,
+    AvoidsShift,
+    OnLeave,
+
+    /// It needs the days off or the hours of rest of the rules
+    Resting,
+
+    /// It's never scheduled with an element on the date, or its partner
+    /// couldn't be scheduled with it
+    KeptApart,
+
+    /// It's on an earlier shift of the date or at its maximum
+    Unavailable,
+
+    /// It could have been scheduled, but it wasn't picked
+    Unpicked,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Rotation => "turn in the rotation",
+            Self::Everyone => "everyone available was needed",
+            Self::Preferred => "preferred day or shift",
+            Self::Role => "role needed on the shift",
+            Self::Debt => "most days owed",
+            Self::FillIn => "next after the rotation",
+            Self::Pair => "pair",
+            Self::Refill => "place freed by a pair",
+            Self::Minimum => "minimum days",
+            Self::AvoidsDay => "avoids the day",
+            Self::AvoidsShift => "avoids the shift",
+            Self::OnLeave => "on leave",
+            Self::Resting => "needs rest",
+            Self::KeptApart => "kept apart by a pair",
+            Self::Unavailable => "on another shift or at maximum",
+            Self::Unpicked => "not picked",
+        })
+    }
+}
+
+/// A decision of the rotation about an element on a shift.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TraceEntry {
+    pub date: NaiveDate,
+
+    /// The name of the shift
+    pub shift: String,
+
+    /// Index of the element in [`Schedule::elements`](crate::schedule::Schedule::elements)
+    pub element_id: usize,
+    pub name: String,
+    pub action: Action,
+    pub reason: Reason,
+
+    /// The debt of the element right after the decision
+    pub debt: i32,
+}
+
+/// The decisions of the rotation, in the order they were made.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct Trace {
+    pub entries: Vec<TraceEntry>,
+}
+
+impl Trace {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Gets the decisions about the element on the date, e.g. to answer why
+    /// it's scheduled on it.
+    pub fn explain(
+        &self,
+        element_id: usize,
+        date: NaiveDate,
+    ) -> impl Iterator<Item = &TraceEntry> {
+        self.entries.iter()
+            .filter(move |entry| {
+                entry.element_id == element_id && entry.date == date
+            })
+    }
+
+    /// Gets the decisions as a JSON array.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.entries)
+            .expect("the trace entries serialize to JSON")
+    }
+}
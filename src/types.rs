@@ -1,6 +1,8 @@
 //! A place where common types reside
 
-/// Supported export file extensions
+use std::{ fmt, path::Path };
+
+/// Supported file extensions for input and export
 #[derive(Debug, Default, PartialEq, Clone)]
 pub enum FileExt {
     #[default]
@@ -8,36 +10,77 @@ pub enum FileExt {
     Csv,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum Day {
     #[default]
     Sunday, Monday, Tuesday, Wednesday, Thursday, Friday, Saturday,
 }
 
-#[derive(Debug, Default, Clone)]
+/// Languages the day names in the input can be in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    Spanish,
+}
+
+/// Names of the days in a language, used to parse the days in the input.
+///
+/// All names are matched case insensitively, and a trailing "." is ignored so
+/// that abbreviations like "Mo." work.
+#[derive(Debug, Clone)]
+pub struct DayNames {
+    /// Names and abbreviations of each day, starting from Sunday
+    pub days: [Vec<String>; 7],
+
+    /// Names of the group of working days, e.g. "weekdays"
+    pub weekdays: Vec<String>,
+
+    /// Names of the group of days off, e.g. "weekends"
+    pub weekends: Vec<String>,
+
+    /// The working days the groups are made of, starting from Sunday like
+    /// [`Calendar::working_days`](crate::calendar::Calendar::working_days).
+    /// Monday to Friday by default.
+    pub working_days: [bool; 7],
+}
+
+/// Error from parsing a day token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DayParseError {
+    /// The token isn't a day, a range of days or a group of days.
+    Unknown(String),
+
+    /// One of the ends of a range like "Mon-Thu" isn't a day.
+    BadRange(String),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum Month {
     #[default]
     January, February, March, April, May, June, July, August, September,
     October, November, December,
 }
 
-impl Day {
-    pub fn from(day: &str) -> Self {
-        let day_uc = day.to_uppercase();
-        let day_str = day_uc.as_str();
-
-        match day_str {
-            "SUNDAY" | "SUN" | "SU" => Day::Sunday,
-            "MONDAY" | "MON" | "MO" | "M" => Day::Monday,
-            "TUESDAY" | "TUE" | "TU" => Day::Tuesday,
-            "WEDNESDAY" | "WED" | "WE" | "W" => Day::Wednesday,
-            "THURSDAY" | "THU" | "TH" => Day::Thursday,
-            "FRIDAY" | "FRI" | "FR" | "F" => Day::Friday,
-            "SATURDAY" | "SAT" | "SA" => Day::Saturday,
+impl FileExt {
+    /// Gets the file extension from the path, if it's a supported one.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
 
-            _ => Day::Sunday,
+        match ext.as_str() {
+            "xlsx" => Some(Self::Xlsx),
+            "csv" => Some(Self::Csv),
+            _ => None,
         }
     }
+}
+
+impl Day {
+    pub const ALL: [Day; 7] = [
+        Day::Sunday, Day::Monday, Day::Tuesday, Day::Wednesday, Day::Thursday,
+        Day::Friday, Day::Saturday,
+    ];
 
     pub fn from_u32(num: u32) -> Self {
         match num {
@@ -53,7 +96,7 @@ impl Day {
         }
     }
 
-    pub fn as_str<'a>(&self) -> &str {
+    pub fn as_str(&self) -> &str {
         match self {
             Day::Sunday => "Sunday",
             Day::Monday => "Monday",
@@ -65,7 +108,12 @@ impl Day {
         }
     }
 
-    pub fn to_u32(&self) -> u32 {
+    /// Whether the day falls on the weekend, i.e. it's Saturday or Sunday.
+    pub fn is_weekend(&self) -> bool {
+        matches!(self, Day::Saturday | Day::Sunday)
+    }
+
+    pub fn to_u32(self) -> u32 {
         match self {
             Day::Sunday => 0,
             Day::Monday => 1,
@@ -78,7 +126,169 @@ impl Day {
     }
 }
 
+impl Language {
+    pub const ALL: [Language; 3] = [
+        Language::English, Language::German, Language::Spanish,
+    ];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+            Self::Spanish => "Español",
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Default for DayNames {
+    fn default() -> Self { Self::from(Language::English) }
+}
+
+impl From<Language> for DayNames {
+    fn from(language: Language) -> Self {
+        match language {
+            Language::English => Self::new(
+                [
+                    &["SUNDAY", "SUN", "SU"],
+                    &["MONDAY", "MON", "MO", "M"],
+                    &["TUESDAY", "TUE", "TUES", "TU"],
+                    &["WEDNESDAY", "WED", "WE", "W"],
+                    &["THURSDAY", "THU", "THUR", "THURS", "TH"],
+                    &["FRIDAY", "FRI", "FR", "F"],
+                    &["SATURDAY", "SAT", "SA"],
+                ],
+                &["WEEKDAYS", "WEEKDAY", "WORKDAYS"],
+                &["WEEKENDS", "WEEKEND"],
+            ),
+
+            Language::German => Self::new(
+                [
+                    &["SONNTAG", "SO"],
+                    &["MONTAG", "MO"],
+                    &["DIENSTAG", "DI"],
+                    &["MITTWOCH", "MI"],
+                    &["DONNERSTAG", "DO"],
+                    &["FREITAG", "FR"],
+                    &["SAMSTAG", "SONNABEND", "SA"],
+                ],
+                &["WOCHENTAGE", "WERKTAGE"],
+                &["WOCHENENDE", "WOCHENENDEN"],
+            ),
+
+            Language::Spanish => Self::new(
+                [
+                    &["DOMINGO", "DOM", "D"],
+                    &["LUNES", "LUN", "L"],
+                    &["MARTES", "MAR", "M"],
+                    &["MIÉRCOLES", "MIERCOLES", "MIÉ", "MIE", "X"],
+                    &["JUEVES", "JUE", "J"],
+                    &["VIERNES", "VIE", "V"],
+                    &["SÁBADO", "SABADO", "SÁB", "SAB", "S"],
+                ],
+                &["ENTRE SEMANA", "LABORABLES", "DÍAS LABORABLES"],
+                &["FIN DE SEMANA", "FINES DE SEMANA"],
+            ),
+        }
+    }
+}
+
+impl DayNames {
+    pub fn new(days: [&[&str]; 7], weekdays: &[&str], weekends: &[&str]) -> Self {
+        let to_vec = |names: &[&str]| names.iter()
+            .map(|name| name.to_uppercase())
+            .collect::<Vec<String>>();
+
+        Self {
+            days: days.map(to_vec),
+            weekdays: to_vec(weekdays),
+            weekends: to_vec(weekends),
+            working_days: Day::ALL.map(|day| !day.is_weekend()),
+        }
+    }
+
+    /// Gets the day with the `name`, if there is one.
+    pub fn day(&self, name: &str) -> Option<Day> {
+        let name = Self::normalize(name);
+
+        self.days.iter()
+            .position(|names| names.contains(&name))
+            .map(|i| Day::from_u32(i as u32))
+    }
+
+    /// Parses a single token of a day list.
+    ///
+    /// The token can be a day ("Mon"), a range of days ("Mon-Thu", which
+    /// wraps around the week for e.g. "Fri-Mon") or a group of days
+    /// ("weekdays", "weekends"). The groups are the [`DayNames::working_days`]
+    /// and the other days.
+    pub fn parse(&self, token: &str) -> Result<Vec<Day>, DayParseError> {
+        let normalized = Self::normalize(token);
+
+        if self.weekdays.contains(&normalized) {
+            return Ok(Day::ALL.into_iter()
+                .filter(|day| self.working_days[day.to_u32() as usize])
+                .collect());
+        }
+
+        if self.weekends.contains(&normalized) {
+            return Ok(Day::ALL.into_iter()
+                .filter(|day| !self.working_days[day.to_u32() as usize])
+                .collect());
+        }
+
+        if let Some(day) = self.day(token) {
+            return Ok(vec![day]);
+        }
+
+        if let Some((from, to)) = token.split_once(['-', '–']) {
+            let (Some(from), Some(to)) = (self.day(from), self.day(to)) else {
+                return Err(DayParseError::BadRange(token.trim().to_owned()));
+            };
+
+            let len = (to.to_u32() + 7 - from.to_u32()) % 7 + 1;
+
+            return Ok((0..len)
+                .map(|i| Day::from_u32((from.to_u32() + i) % 7))
+                .collect());
+        }
+
+        Err(DayParseError::Unknown(token.trim().to_owned()))
+    }
+
+    fn normalize(name: &str) -> String {
+        name.trim()
+            .trim_end_matches('.')
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .to_uppercase()
+    }
+}
+
+impl fmt::Display for DayParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(token) => write!(f, "Unknown day \"{}\"", token),
+            Self::BadRange(token) => write!(f, "Bad range of days \"{}\"", token),
+        }
+    }
+}
+
+impl std::error::Error for DayParseError {}
+
 impl Month {
+    pub const ALL: [Month; 12] = [
+        Self::January, Self::February, Self::March, Self::April, Self::May,
+        Self::June, Self::July, Self::August, Self::September, Self::October,
+        Self::November, Self::December,
+    ];
+
     pub fn from_u32(num: u32) -> Self {
         match num {
             1 => Self::January,
@@ -97,7 +307,7 @@ impl Month {
         }
     }
 
-    pub fn as_str<'a>(&self) -> &str {
+    pub fn as_str(&self) -> &str {
         match self {
             Self::January => "January",
             Self::February => "February",
@@ -113,5 +323,27 @@ impl Month {
             Self::December => "December",
         }
     }
+
+    pub fn to_u32(self) -> u32 {
+        match self {
+            Self::January => 1,
+            Self::February => 2,
+            Self::March => 3,
+            Self::April => 4,
+            Self::May => 5,
+            Self::June => 6,
+            Self::July => 7,
+            Self::August => 8,
+            Self::September => 9,
+            Self::October => 10,
+            Self::November => 11,
+            Self::December => 12,
+        }
+    }
 }
 
+impl fmt::Display for Month {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
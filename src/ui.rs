@@ -1,18 +1,49 @@
-use std::path::PathBuf;
+use std::{ fmt, iter, path::PathBuf };
 
 use iced::{
     widget::{
-        button, column, container, container::Style as ContainerStyle, row,
-        text, text::Style as TextStyle,
+        button, checkbox, column, container, container::Style as ContainerStyle,
+        pick_list, row, scrollable, slider, text, text::Style as TextStyle,
+        text_input,
     },
     Alignment, Border, Color, Element, Length, Padding, Task, Background,
 };
 
-use opener;
-use chrono::Local;
+use chrono::{ Datelike, Local };
 use native_dialog::DialogBuilder;
+use rand::{ rng, Rng };
 
-use crate::{ types::FileExt, reader::Reader, writer::Writer, worker };
+use crate::{
+    calendar::Calendar, reader::{ ReadError, Reader }, schedule::Schedule,
+    scheduler::{ Registry, Scheduler }, solver::{ self, Score, Weights },
+    stats::ScheduleStats,
+    types::{ Day, DayNames, FileExt, Language, Month },
+    validator::{ self, Severity, ValidationReport },
+    worker::Rules,
+    writer::Writer,
+};
+
+/// The most elements that can be picked for a day in the UI
+const MAX_HEADCOUNT: usize = 50;
+
+/// The longest streak of days that can be picked in the UI
+const MAX_CONSECUTIVE_DAYS: usize = 14;
+
+/// The hours of rest between shifts that can be picked in the UI
+const REST_HOURS: [usize; 6] = [8, 10, 11, 12, 16, 24];
+
+/// A limit that can be turned off, shown in the pick lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limit(pub Option<usize>);
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(limit) => write!(f, "{}", limit),
+            None => write!(f, "No limit"),
+        }
+    }
+}
 
 #[derive(Default, PartialEq)]
 pub enum ScreenState {
@@ -31,6 +62,54 @@ pub struct Window {
     pub path: Option<PathBuf>,
     pub file_name: String,
     pub exported: bool,
+
+    /// The error from reading the selected file, if any
+    pub read_error: Option<ReadError>,
+
+    /// The error from reading the separate holidays file, if any
+    pub holidays_error: Option<ReadError>,
+
+    /// The error from reading the separate shifts file, if any
+    pub shifts_error: Option<ReadError>,
+
+    /// The error from reading the separate coverage file, if any
+    pub coverage_error: Option<ReadError>,
+
+    /// The error from reading the previous period's ledger, if any
+    pub ledger_error: Option<ReadError>,
+
+    /// Issues found in the elements that were read
+    pub validation: ValidationReport,
+
+    /// Language of the day names in the input
+    pub language: Language,
+
+    /// Rules for the next run of the scheduler
+    pub rules: Rules,
+
+    /// The month to schedule, along with the number of elements each day
+    pub calendar: Calendar,
+
+    /// The schedulers that can be picked
+    pub schedulers: Registry,
+
+    /// The name of the scheduler the next schedule is generated with, the
+    /// first one registered if there's no scheduler with the name
+    pub scheduler_name: String,
+
+    /// The last generated schedule
+    pub schedule: Option<Schedule>,
+
+    /// How well the last generated schedule meets the objectives of the
+    /// optimal scheduler, to compare the schedulers
+    pub score: Score,
+
+    /// The statistics of the last generated schedule
+    pub stats: ScheduleStats,
+
+    /// The seed typed in to reproduce a schedule, a random one is used if it's
+    /// empty
+    pub seed_input: String,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -39,14 +118,64 @@ pub enum WindowMessage {
     None,
     ChooseFile,
 
+    /// Choose a separate file with the holidays
+    ChooseHolidaysFile,
+
+    /// Choose a separate file with the shifts
+    ChooseShiftsFile,
+
+    /// Choose a separate file with the roles each shift needs
+    ChooseCoverageFile,
+
+    /// Choose the previous period's export or ledger file
+    ChooseLedgerFile,
+    LanguageSelected(Language),
+    PreferenceWeightChanged(f32),
+
+    /// The most days in a row an element can be scheduled on
+    MaxConsecutiveDaysSelected(Limit),
+
+    /// The fewest days off after the most days in a row
+    MinDaysOffSelected(usize),
+
+    /// The fewest hours of rest between two shifts
+    MinRestHoursSelected(Limit),
+
+    /// The name of the scheduler to generate the schedule with
+    SchedulerSelected(String),
+    MonthSelected(Month),
+    YearSelected(i32),
+
+    /// Number of elements to schedule on every day
+    AllHeadcountsSelected(usize),
+
+    /// Number of elements to schedule on a day of the week
+    HeadcountSelected(Day, usize),
+
+    /// Whether a day of the week is scheduled
+    WorkingDayToggled(Day, bool),
+    SeedChanged(String),
+
     /// Generate the schedule
     Generate,
 
-    /// 
+    /// Runs the scheduler once the "generating" state is shown
     Generating,
 
+    /// Generate the schedule again with a new seed
+    Regenerate,
+
     /// Export to file
     Export(FileExt),
+
+    /// Export the ledger alone, to carry it over to the next period
+    ExportLedger,
+
+    /// Whether to record why each element is scheduled
+    TraceToggled(bool),
+
+    /// Export the decisions of the rotation as JSON
+    ExportTrace,
     GoToGitHub,
     Reset,
 }
@@ -54,12 +183,45 @@ pub enum WindowMessage {
 impl Window {
     pub fn new() -> Self { Self::default() }
 
+    /// Creates the window with other schedulers than the ones that come with
+    /// the crate, e.g. with a team's own scheduler registered along with them.
+    pub fn with_schedulers(schedulers: Registry) -> Self {
+        Self { schedulers, ..Self::default() }
+    }
+
+    /// Gets the scheduler that's been picked, or the first one registered.
+    ///
+    /// Returns `None` if there are no schedulers to pick.
+    fn scheduler(&self) -> Option<&dyn Scheduler> {
+        self.schedulers.get(&self.scheduler_name)
+            .or_else(|| self.schedulers.first())
+    }
+
     fn reset(&mut self) {
         self.screen_state = ScreenState::Start;
         self.reader = Reader::default();
         self.writer = Writer::default();
         self.path = None;
         self.file_name = String::default();
+        self.read_error = None;
+        self.holidays_error = None;
+        self.shifts_error = None;
+        self.coverage_error = None;
+        self.ledger_error = None;
+        self.calendar.set_shifts(Vec::new());
+        self.calendar.coverage = Vec::new();
+        self.schedule = None;
+        self.seed_input = String::default();
+        self.validation = ValidationReport::default();
+    }
+
+    /// Validates the elements read from the file against the current settings.
+    fn revalidate(&mut self) {
+        if self.path.is_some() && self.read_error.is_none() {
+            self.calendar.set_shifts(self.reader.shifts.clone());
+            self.calendar.coverage = self.reader.coverage.clone();
+            self.validation = validator::validate(&self.reader, &self.calendar);
+        }
     }
 
     pub fn update(&mut self, message: WindowMessage) -> Task<WindowMessage> {
@@ -68,22 +230,33 @@ impl Window {
 
             WindowMessage::ChooseFile => {
                 let path = DialogBuilder::file()
-                    .add_filter("Spreadsheet Files", &[ "xlsx", "csv" ])
+                    .add_filter("Spreadsheet Files", ["xlsx", "csv"])
                     .open_single_file()
                     .show()
                     .unwrap();
 
                 if let Some(p) = path {
                     self.path = Some(p.clone());
+                    self.reader.day_names = DayNames::from(self.language);
+                    self.reader.day_names.working_days =
+                        self.calendar.working_days;
 
-                    if let Some(path_last) = p.iter().last() {
+                    if let Some(path_last) = p.file_name() {
                         if let Some(path_str) = path_last.to_str() {
                             self.file_name = path_str.to_owned();
                         }
                     }
 
                     self.screen_state = ScreenState::FileSelected;
-                    self.reader.read(p.as_path());
+                    match self.reader.read(p.as_path()) {
+                        Ok(()) => {
+                            self.revalidate();
+                        }
+
+                        Err(e) => {
+                            self.read_error = Some(e);
+                        }
+                    }
 
                     return Task::done(WindowMessage::None);
                 }
@@ -91,25 +264,210 @@ impl Window {
                 Task::none()
             }
 
+            WindowMessage::ChooseHolidaysFile => {
+                let path = DialogBuilder::file()
+                    .add_filter("Spreadsheet Files", ["xlsx", "csv"])
+                    .open_single_file()
+                    .show()
+                    .unwrap();
+
+                if let Some(p) = path {
+                    self.holidays_error = None;
+
+                    if let Err(e) = self.reader.read_holidays(p.as_path()) {
+                        self.holidays_error = Some(e);
+                    }
+
+                    self.revalidate();
+                }
+
+                Task::none()
+            }
+
+            WindowMessage::ChooseShiftsFile => {
+                let path = DialogBuilder::file()
+                    .add_filter("Spreadsheet Files", ["xlsx", "csv"])
+                    .open_single_file()
+                    .show()
+                    .unwrap();
+
+                if let Some(p) = path {
+                    self.shifts_error = None;
+
+                    if let Err(e) = self.reader.read_shifts(p.as_path()) {
+                        self.shifts_error = Some(e);
+                    }
+
+                    self.revalidate();
+                }
+
+                Task::none()
+            }
+
+            WindowMessage::ChooseCoverageFile => {
+                let path = DialogBuilder::file()
+                    .add_filter("Spreadsheet Files", ["xlsx", "csv"])
+                    .open_single_file()
+                    .show()
+                    .unwrap();
+
+                if let Some(p) = path {
+                    self.coverage_error = None;
+
+                    if let Err(e) = self.reader.read_coverage(p.as_path()) {
+                        self.coverage_error = Some(e);
+                    }
+
+                    self.revalidate();
+                }
+
+                Task::none()
+            }
+
+            WindowMessage::ChooseLedgerFile => {
+                let path = DialogBuilder::file()
+                    .add_filter("Spreadsheet Files", ["xlsx", "csv"])
+                    .open_single_file()
+                    .show()
+                    .unwrap();
+
+                if let Some(p) = path {
+                    self.ledger_error = None;
+
+                    if let Err(e) = self.reader.read_ledger(p.as_path()) {
+                        self.ledger_error = Some(e);
+                    }
+
+                    self.revalidate();
+                }
+
+                Task::none()
+            }
+
+            WindowMessage::LanguageSelected(language) => {
+                self.language = language;
+
+                Task::none()
+            }
+
+            WindowMessage::PreferenceWeightChanged(weight) => {
+                self.rules.preference_weight = weight;
+
+                Task::none()
+            }
+
+            WindowMessage::MaxConsecutiveDaysSelected(Limit(days)) => {
+                self.rules.max_consecutive_days = days;
+
+                Task::none()
+            }
+
+            WindowMessage::MinDaysOffSelected(days) => {
+                self.rules.min_days_off = days;
+
+                Task::none()
+            }
+
+            WindowMessage::MinRestHoursSelected(Limit(hours)) => {
+                self.rules.min_rest_hours = hours;
+
+                Task::none()
+            }
+
+            WindowMessage::SchedulerSelected(name) => {
+                self.scheduler_name = name;
+
+                Task::none()
+            }
+
+            WindowMessage::MonthSelected(month) => {
+                self.calendar.month = month;
+
+                Task::none()
+            }
+
+            WindowMessage::YearSelected(year) => {
+                self.calendar.year = year;
+
+                Task::none()
+            }
+
+            WindowMessage::AllHeadcountsSelected(headcount) => {
+                self.calendar.set_all_headcounts(headcount);
+                self.revalidate();
+
+                Task::none()
+            }
+
+            WindowMessage::HeadcountSelected(day, headcount) => {
+                self.calendar.set_headcount(day, headcount);
+                self.revalidate();
+
+                Task::none()
+            }
+
+            WindowMessage::WorkingDayToggled(day, working) => {
+                self.calendar.set_working_day(day, working);
+                self.reader.set_working_days(self.calendar.working_days);
+                self.revalidate();
+
+                Task::none()
+            }
+
+            WindowMessage::SeedChanged(seed) => {
+                self.seed_input = seed.chars()
+                    .filter(|c| c.is_ascii_digit())
+                    .collect();
+
+                Task::none()
+            }
+
             WindowMessage::Generate => {
+                self.rules.seed = self.seed_input.parse().ok();
                 self.screen_state = ScreenState::ScheduleGenerating;
 
                 Task::done(WindowMessage::Generating)
             },
 
             WindowMessage::Generating => {
-                let now = Local::now();
-                let output = worker::process(&self.reader.elements, now, 10);
+                self.calendar.holidays = self.reader.holidays.clone();
+                self.calendar.set_shifts(self.reader.shifts.clone());
+                self.calendar.coverage = self.reader.coverage.clone();
+                self.rules.pairings = self.reader.pairings.clone();
+                self.rules.carry_over = self.reader.ledger.clone();
 
-                self.writer.init(10, now, &output);
+                let Some(scheduler) = self.scheduler() else {
+                    self.screen_state = ScreenState::FileSelected;
+                    return Task::none();
+                };
 
-                println!("{:?}", output);
+                let schedule = scheduler.schedule(
+                    &self.reader.elements,
+                    &self.calendar,
+                    &self.rules,
+                );
+                self.score = solver::evaluate(
+                    &schedule, &self.rules, &Weights::default()
+                );
+                self.stats = ScheduleStats::new(&schedule);
+
+                self.writer.init(&schedule);
+
+                self.seed_input = schedule.seed.to_string();
+                self.schedule = Some(schedule);
 
                 self.screen_state = ScreenState::ScheduleGenerated;
 
                 Task::none()
             }
 
+            WindowMessage::Regenerate => {
+                self.seed_input = rng().random::<u64>().to_string();
+                self.exported = false;
+
+                Task::done(WindowMessage::Generate)
+            }
+
             WindowMessage::Export(file) => {
                 self.writer.export(file);
 
@@ -117,6 +475,26 @@ impl Window {
                 Task::none()
             },
 
+            WindowMessage::ExportLedger => {
+                self.writer.export_ledger();
+
+                self.exported = true;
+                Task::none()
+            },
+
+            WindowMessage::TraceToggled(trace) => {
+                self.rules.trace = trace;
+
+                Task::none()
+            }
+
+            WindowMessage::ExportTrace => {
+                self.writer.export_trace();
+
+                self.exported = true;
+                Task::none()
+            },
+
             WindowMessage::GoToGitHub => {
                 let _ = opener::open(
                     "https://github.com/abhinavgunwant/schedulez"
@@ -133,7 +511,7 @@ impl Window {
         }
     }
 
-    pub fn view(&self) -> Element<WindowMessage> {
+    pub fn view(&self) -> Element<'_, WindowMessage> {
         let mut cols = column![];
 
         if self.screen_state == ScreenState::Start {
@@ -153,13 +531,31 @@ impl Window {
                     .width(Length::Fill)
             );
 
+            cols = cols.push(
+                container(row![
+                    text("Day names in: "),
+                    pick_list(
+                        Language::ALL,
+                        Some(self.language),
+                        WindowMessage::LanguageSelected,
+                    ),
+                ].align_y(Alignment::Center))
+                    .align_x(Alignment::Center)
+                    .width(Length::Fill)
+                    .padding(Padding {
+                        top: 8.0,
+                        bottom: 8.0,
+                        ..Padding::default()
+                    })
+            );
+
             cols = cols.push(
                 container(row![
                     text("See "),
                     button("github.com/abhinavgunwant/schedulez")
                         .style(|_,_| button::Style {
                             background: None,
-                            text_color: iced::color!(100, 100, 255).into(),
+                            text_color: iced::color!(100, 100, 255),
                             border: Border { width: 0.0, ..Border::default() },
                             ..button::Style::default()
                         })
@@ -182,13 +578,34 @@ impl Window {
                     .width(Length::Fill)
             );
 
-            cols = cols.push(
-                container(row![
+            let month_row = if self.screen_state == ScreenState::FileSelected {
+                let this_year = Local::now().year();
+
+                row![
+                    text("Schedule for month: "),
+                    pick_list(
+                        Month::ALL,
+                        Some(self.calendar.month),
+                        WindowMessage::MonthSelected,
+                    ),
+                    text(" Year: "),
+                    pick_list(
+                        (this_year - 1..=this_year + 5).collect::<Vec<i32>>(),
+                        Some(self.calendar.year),
+                        WindowMessage::YearSelected,
+                    ),
+                ]
+            } else {
+                row![
                     text("Schedule for month: "),
-                    text("May "),
+                    text(format!("{} ", self.calendar.month.as_str())),
                     text("Year: "),
-                    text("2025"),
-                ])
+                    text(self.calendar.year),
+                ]
+            };
+
+            cols = cols.push(
+                container(month_row.align_y(Alignment::Center))
                     .align_x(Alignment::Center)
                     .width(Length::Fill)
                     .padding(Padding {
@@ -199,18 +616,366 @@ impl Window {
         }
 
         if self.screen_state == ScreenState::FileSelected {
-            cols = cols.push(
-                container(
-                    button("Generate Schedule")
-                        .on_press(WindowMessage::Generate)
-                )
-                    .align_x(Alignment::Center)
-                    .width(Length::Fill)
-                    .padding(Padding {
-                        bottom: 8.0,
-                        ..Padding::default()
-                    })
-            );
+            if let Some(e) = &self.read_error {
+                cols = cols.push(
+                    container(
+                        text(e.to_string())
+                            .style(|_| TextStyle {
+                                color: Some(Color::from_rgb(1.0, 0.5, 0.5)),
+                            })
+                    )
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+            } else {
+                if !self.validation.issues.is_empty() {
+                    let mut issues = column![];
+
+                    for issue in self.validation.issues.iter() {
+                        let color = match issue.severity {
+                            Severity::Warning => Color::from_rgb(1.0, 0.85, 0.4),
+                            Severity::Error => Color::from_rgb(1.0, 0.5, 0.5),
+                        };
+
+                        issues = issues.push(
+                            text(issue.to_string())
+                                .style(move |_| TextStyle { color: Some(color) })
+                        );
+                    }
+
+                    cols = cols.push(
+                        container(scrollable(issues).height(Length::Fixed(120.0)))
+                            .align_x(Alignment::Center)
+                            .width(Length::Fill)
+                            .padding(Padding {
+                                left: 16.0,
+                                right: 16.0,
+                                bottom: 8.0,
+                                ..Padding::default()
+                            })
+                    );
+                }
+
+                let headcounts = (0..=MAX_HEADCOUNT).collect::<Vec<usize>>();
+                let working_days = self.calendar.working_days();
+                let all_headcount = working_days.first()
+                    .map(|day| self.calendar.headcount(*day))
+                    .filter(|n| working_days.iter()
+                        .all(|day| self.calendar.headcount(*day) == *n));
+
+                let mut day_toggles = row![].spacing(8).align_y(Alignment::Center);
+
+                for day in Day::ALL {
+                    day_toggles = day_toggles.push(
+                        checkbox(
+                            &day.as_str()[..3],
+                            self.calendar.is_working_day(day),
+                        )
+                            .on_toggle(move |working| {
+                                WindowMessage::WorkingDayToggled(day, working)
+                            })
+                    );
+                }
+
+                cols = cols.push(
+                    container(row![text("Working days: "), day_toggles]
+                        .align_y(Alignment::Center))
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                cols = cols.push(
+                    container(row![
+                        text("People per day: "),
+                        pick_list(
+                            headcounts.clone(),
+                            all_headcount,
+                            WindowMessage::AllHeadcountsSelected,
+                        )
+                            .placeholder("Varies"),
+                    ].align_y(Alignment::Center))
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                let mut day_headcounts = row![].spacing(4).align_y(Alignment::Center);
+
+                for day in working_days {
+                    day_headcounts = day_headcounts
+                        .push(text(format!("{}:", &day.as_str()[..3])))
+                        .push(pick_list(
+                            headcounts.clone(),
+                            Some(self.calendar.headcount(day)),
+                            move |n| WindowMessage::HeadcountSelected(day, n),
+                        ));
+                }
+
+                cols = cols.push(
+                    container(day_headcounts)
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                cols = cols.push(
+                    container(row![
+                        text("Weight of preferred days: "),
+                        slider(
+                            0.0..=1.0,
+                            self.rules.preference_weight,
+                            WindowMessage::PreferenceWeightChanged,
+                        )
+                            .step(0.05)
+                            .width(Length::Fixed(160.0)),
+                        text(format!(
+                            " {:.0}%", self.rules.preference_weight * 100.0
+                        )),
+                    ].align_y(Alignment::Center))
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                let consecutive_limits: Vec<Limit> = iter::once(Limit(None))
+                    .chain((1..=MAX_CONSECUTIVE_DAYS).map(|n| Limit(Some(n))))
+                    .collect();
+
+                let mut rest_row = row![
+                    text("Most days in a row: "),
+                    pick_list(
+                        consecutive_limits,
+                        Some(Limit(self.rules.max_consecutive_days)),
+                        WindowMessage::MaxConsecutiveDaysSelected,
+                    ),
+                ].spacing(4).align_y(Alignment::Center);
+
+                if self.rules.max_consecutive_days.is_some() {
+                    rest_row = rest_row.push(text(" then days off: "));
+                    rest_row = rest_row.push(pick_list(
+                        (0..=7).collect::<Vec<usize>>(),
+                        Some(self.rules.min_days_off),
+                        WindowMessage::MinDaysOffSelected,
+                    ));
+                }
+
+                // Only shifts with times have hours of rest between them
+                if !self.calendar.shifts.iter().all(|shift| shift.is_default()) {
+                    let rest_limits: Vec<Limit> = iter::once(Limit(None))
+                        .chain(REST_HOURS.iter().map(|n| Limit(Some(*n))))
+                        .collect();
+
+                    rest_row = rest_row.push(text(" Hours of rest: "));
+                    rest_row = rest_row.push(pick_list(
+                        rest_limits,
+                        Some(Limit(self.rules.min_rest_hours)),
+                        WindowMessage::MinRestHoursSelected,
+                    ));
+                }
+
+                cols = cols.push(
+                    container(rest_row)
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                let mut holidays_row = row![
+                    text(format!("Holidays: {} ", self.reader.holidays.len())),
+                    button("Choose holidays file")
+                        .on_press(WindowMessage::ChooseHolidaysFile),
+                ].align_y(Alignment::Center);
+
+                if let Some(e) = &self.holidays_error {
+                    holidays_row = holidays_row.push(
+                        text(format!(" {}", e))
+                            .style(|_| TextStyle {
+                                color: Some(Color::from_rgb(1.0, 0.5, 0.5)),
+                            })
+                    );
+                }
+
+                cols = cols.push(
+                    container(holidays_row)
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                let shift_names: Vec<&str> = self.calendar.shifts.iter()
+                    .map(|shift| shift.name.as_str())
+                    .collect();
+
+                let mut shifts_row = row![
+                    text(format!("Shifts: {} ", shift_names.join(", "))),
+                    button("Choose shifts file")
+                        .on_press(WindowMessage::ChooseShiftsFile),
+                ].align_y(Alignment::Center);
+
+                if let Some(e) = &self.shifts_error {
+                    shifts_row = shifts_row.push(
+                        text(format!(" {}", e))
+                            .style(|_| TextStyle {
+                                color: Some(Color::from_rgb(1.0, 0.5, 0.5)),
+                            })
+                    );
+                }
+
+                cols = cols.push(
+                    container(shifts_row)
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                let mut coverage_row = row![
+                    text(format!(
+                        "Roles needed: {} ",
+                        self.calendar.coverage.len(),
+                    )),
+                    button("Choose coverage file")
+                        .on_press(WindowMessage::ChooseCoverageFile),
+                ].align_y(Alignment::Center);
+
+                if let Some(e) = &self.coverage_error {
+                    coverage_row = coverage_row.push(
+                        text(format!(" {}", e))
+                            .style(|_| TextStyle {
+                                color: Some(Color::from_rgb(1.0, 0.5, 0.5)),
+                            })
+                    );
+                }
+
+                cols = cols.push(
+                    container(coverage_row)
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                let mut ledger_row = row![
+                    text(format!(
+                        "Carried over: {} ",
+                        self.reader.ledger.entries.len(),
+                    )),
+                    button("Choose previous export or ledger")
+                        .on_press(WindowMessage::ChooseLedgerFile),
+                ].align_y(Alignment::Center);
+
+                if let Some(e) = &self.ledger_error {
+                    ledger_row = ledger_row.push(
+                        text(format!(" {}", e))
+                            .style(|_| TextStyle {
+                                color: Some(Color::from_rgb(1.0, 0.5, 0.5)),
+                            })
+                    );
+                }
+
+                cols = cols.push(
+                    container(ledger_row)
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                cols = cols.push(
+                    container(row![
+                        text("Scheduler: "),
+                        pick_list(
+                            self.schedulers.names(),
+                            self.scheduler()
+                                .map(|scheduler| scheduler.name().to_owned()),
+                            WindowMessage::SchedulerSelected,
+                        ),
+                    ].align_y(Alignment::Center))
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                cols = cols.push(
+                    container(
+                        checkbox(
+                            "Record why each person is scheduled (rotation only)",
+                            self.rules.trace,
+                        )
+                            .on_toggle(WindowMessage::TraceToggled)
+                    )
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                cols = cols.push(
+                    container(row![
+                        text("Seed: "),
+                        text_input("Random", &self.seed_input)
+                            .on_input(WindowMessage::SeedChanged)
+                            .width(Length::Fixed(200.0)),
+                    ].align_y(Alignment::Center))
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                cols = cols.push(
+                    container(
+                        button("Generate Schedule")
+                            .on_press_maybe(
+                                (!self.validation.has_errors()
+                                    && self.scheduler().is_some())
+                                    .then_some(WindowMessage::Generate)
+                            )
+                    )
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+            }
 
             cols = cols.push(
                 container(button("Reset").on_press(WindowMessage::Reset))
@@ -232,6 +997,122 @@ impl Window {
         }
 
         if self.screen_state == ScreenState::ScheduleGenerated {
+            if let Some(schedule) = &self.schedule {
+                let scheduled_days = schedule.by_date()
+                    .filter(|(_, assignments)| !assignments.is_empty())
+                    .count();
+
+                cols = cols.push(
+                    container(text(format!(
+                        "{} assignments on {} days (seed {})",
+                        schedule.assignments.len(),
+                        scheduled_days,
+                        schedule.seed,
+                    )))
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                cols = cols.push(
+                    container(text(format!("{}", self.score)))
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                cols = cols.push(
+                    container(text(format!(
+                        "Days per person: {:.1} on average, {:.2} standard \
+                        deviation. Shifts not filled: {} ({} empty places)",
+                        self.stats.mean,
+                        self.stats.std_dev,
+                        self.stats.unmet_slots,
+                        self.stats.missing_places,
+                    )))
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                let cell = |value: String, width: f32| {
+                    text(value).width(Length::Fixed(width))
+                };
+                let mut stats_table = column![
+                    row(
+                        ["Name", "Days"].into_iter()
+                            .chain(Day::ALL.iter().map(|day| &day.as_str()[..3]))
+                            .chain(["Wknd", "Streak", "Debt"])
+                            .enumerate()
+                            .map(|(i, title)| cell(
+                                title.to_owned(),
+                                if i == 0 { 160.0 } else { 48.0 },
+                            ).into())
+                    ),
+                ];
+
+                for elem in self.stats.elements.iter() {
+                    stats_table = stats_table.push(row(
+                        iter::once(cell(elem.name.clone(), 160.0))
+                            .chain(iter::once(elem.total)
+                                .chain(elem.by_day)
+                                .chain([
+                                    elem.weekend,
+                                    elem.longest_streak,
+                                ])
+                                .map(|value| cell(value.to_string(), 48.0)))
+                            .chain(iter::once(cell(elem.debt.to_string(), 48.0)))
+                            .map(Element::from)
+                    ));
+                }
+
+                cols = cols.push(
+                    container(scrollable(stats_table).height(Length::Fixed(120.0)))
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .padding(Padding {
+                            left: 16.0,
+                            right: 16.0,
+                            bottom: 8.0,
+                            ..Padding::default()
+                        })
+                );
+
+                if !schedule.warnings.is_empty() {
+                    let mut warnings = column![];
+
+                    for warning in schedule.warnings.iter() {
+                        warnings = warnings.push(
+                            text(format!("Warning: {}", warning))
+                                .style(|_| TextStyle {
+                                    color: Some(Color::from_rgb(1.0, 0.85, 0.4)),
+                                })
+                        );
+                    }
+
+                    cols = cols.push(
+                        container(scrollable(warnings).height(Length::Fixed(120.0)))
+                            .align_x(Alignment::Center)
+                            .width(Length::Fill)
+                            .padding(Padding {
+                                left: 16.0,
+                                right: 16.0,
+                                bottom: 8.0,
+                                ..Padding::default()
+                            })
+                    );
+                }
+            }
+
             cols = cols.push(
                 container(text("Export as:"))
                     .align_x(Alignment::Center)
@@ -242,19 +1123,41 @@ impl Window {
                     })
             );
 
-            cols = cols.push(
-                container(row![
+            let mut export_row = row![
+                container(
+                    button(".xlsx")
+                        .on_press(WindowMessage::Export(FileExt::Xlsx))
+                )
+                    .padding(Padding {
+                        right: 8.0,
+                        ..Padding::default()
+                    }),
+
+                container(button(".csv (Coming Soon)"))
+                    .padding(Padding {
+                        right: 8.0,
+                        ..Padding::default()
+                    }),
+
+                button("Ledger (.csv)")
+                    .on_press(WindowMessage::ExportLedger),
+            ];
+
+            if self.schedule.as_ref().is_some_and(|s| !s.trace.is_empty()) {
+                export_row = export_row.push(
                     container(
-                        button(".xlsx")
-                            .on_press(WindowMessage::Export(FileExt::Xlsx))
+                        button("Trace (.json)")
+                            .on_press(WindowMessage::ExportTrace)
                     )
                         .padding(Padding {
-                            right: 8.0,
+                            left: 8.0,
                             ..Padding::default()
-                        }),
+                        })
+                );
+            }
 
-                    button(".csv (Coming Soon)"),
-                ])
+            cols = cols.push(
+                container(export_row)
                     .align_x(Alignment::Center)
                     .width(Length::Fill)
                     .padding(Padding {
@@ -281,7 +1184,10 @@ impl Window {
             }
 
             cols = cols.push(
-                container(button("Reset").on_press(WindowMessage::Reset))
+                container(row![
+                    button("Regenerate").on_press(WindowMessage::Regenerate),
+                    button("Reset").on_press(WindowMessage::Reset),
+                ].spacing(8))
                     .align_x(Alignment::Center)
                     .width(Length::Fill)
             );
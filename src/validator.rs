@@ -0,0 +1,368 @@
+//! Checks the elements read from the input before a schedule is generated.
+
+use std::{ collections::HashMap, fmt };
+
+use chrono::Datelike;
+
+use crate::{
+    calendar::Calendar, reader::{ column_name, ReadError, Reader },
+    types::Day,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The schedule can be generated, but may not be what's expected.
+    Warning,
+
+    /// The input has to be fixed before generating the schedule.
+    Error,
+}
+
+/// A problem found in the input.
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub severity: Severity,
+
+    /// The (1-based) row of the input the issue is on, if it's about a single
+    /// row.
+    pub row: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "Warning",
+            Severity::Error => "Error",
+        };
+
+        match self.row {
+            Some(row) => write!(f, "{} (row {}): {}", severity, row, self.message),
+            None => write!(f, "{}: {}", severity, self.message),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ValidationReport {
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    /// Whether any of the issues stops the schedule from being generated.
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|issue| issue.severity == Severity::Error)
+    }
+
+    fn push(&mut self, severity: Severity, row: Option<usize>, message: String) {
+        self.issues.push(Issue { severity, row, message });
+    }
+}
+
+/// Validates the elements read by the `reader`.
+///
+/// Params:
+/// - `reader` The reader the input file has been read with
+/// - `calendar` The month to schedule, along with the number of elements each
+///   day needs
+pub fn validate(reader: &Reader, calendar: &Calendar) -> ValidationReport {
+    let mut report = ValidationReport::default();
+
+    for e in reader.row_errors.iter() {
+        match e {
+            ReadError::BadDayToken { row, column, token } => report.push(
+                Severity::Error,
+                Some(*row),
+                format!(
+                    "Unknown day \"{}\" in column {}",
+                    token,
+                    column_name(*column),
+                ),
+            ),
+
+            ReadError::BadDate { row, column, value } => report.push(
+                Severity::Error,
+                Some(*row),
+                format!(
+                    "Unknown date \"{}\" in column {}",
+                    value,
+                    column_name(*column),
+                ),
+            ),
+
+            ReadError::BadTime { row, column, value } => report.push(
+                Severity::Error,
+                Some(*row),
+                format!(
+                    "Unknown time \"{}\" in column {}",
+                    value,
+                    column_name(*column),
+                ),
+            ),
+
+            ReadError::BadPairRule { row, column, value } => report.push(
+                Severity::Error,
+                Some(*row),
+                format!(
+                    "Unknown pair rule \"{}\" in column {}",
+                    value,
+                    column_name(*column),
+                ),
+            ),
+
+            ReadError::BadNumber { row, column, value } => report.push(
+                Severity::Error,
+                Some(*row),
+                format!(
+                    "Not a number \"{}\" in column {}",
+                    value,
+                    column_name(*column),
+                ),
+            ),
+
+            e => report.push(Severity::Error, None, e.to_string()),
+        }
+    }
+
+    if reader.elements.is_empty() {
+        report.push(
+            Severity::Error,
+            None,
+            "No elements found in the file".to_owned(),
+        );
+
+        return report;
+    }
+
+    let working_days = calendar.working_days();
+
+    if working_days.is_empty() {
+        report.push(
+            Severity::Error,
+            None,
+            "No working days are selected".to_owned(),
+        );
+
+        return report;
+    }
+
+    // Dates that can be scheduled, before taking anyone's leave into account
+    let scheduled_dates = calendar.dates()
+        .filter(|date| calendar.is_working_day(
+            Day::from_u32(date.weekday().num_days_from_sunday())
+        ))
+        .filter(|date| !reader.holidays.iter()
+            .any(|holiday| holiday.date == *date))
+        .count();
+
+    // Rows of the elements by their name, to find the duplicates
+    let mut rows_by_name: HashMap<&str, usize> = HashMap::new();
+
+    for elem in reader.elements.iter() {
+        let name = elem.text.trim();
+
+        if name.is_empty() {
+            report.push(
+                Severity::Error,
+                Some(elem.row),
+                "The name is blank".to_owned(),
+            );
+        } else if let Some(first_row) = rows_by_name.get(name) {
+            report.push(
+                Severity::Error,
+                Some(elem.row),
+                format!("\"{}\" is already on row {}", name, first_row),
+            );
+        } else {
+            rows_by_name.insert(name, elem.row);
+        }
+
+        if working_days.iter().all(|day| elem.avoid_days.contains(day)) {
+            report.push(
+                Severity::Warning,
+                Some(elem.row),
+                format!("\"{}\" avoids every working day", name),
+            );
+        }
+
+        match (elem.min_assignments, elem.max_assignments) {
+            (Some(min), Some(max)) if min > max => report.push(
+                Severity::Error,
+                Some(elem.row),
+                format!(
+                    "The minimum of {} days is more than the maximum of {}",
+                    min,
+                    max,
+                ),
+            ),
+
+            (Some(min), _) if min > scheduled_dates => report.push(
+                Severity::Warning,
+                Some(elem.row),
+                format!(
+                    "\"{}\" needs at least {} days, but only {} are scheduled",
+                    name,
+                    min,
+                    scheduled_dates,
+                ),
+            ),
+
+            _ => {}
+        }
+
+        for shift in elem.prefer_shifts.iter().chain(elem.avoid_shifts.iter()) {
+            if calendar.shift_index(shift).is_none() {
+                report.push(
+                    Severity::Error,
+                    Some(elem.row),
+                    format!("Unknown shift \"{}\"", shift),
+                );
+            }
+        }
+
+        if calendar.shifts.iter().all(|shift| elem.avoid_shifts.iter()
+            .any(|name| shift.is_named(name)))
+        {
+            report.push(
+                Severity::Warning,
+                Some(elem.row),
+                format!("\"{}\" avoids every shift", name),
+            );
+        }
+    }
+
+    for coverage in calendar.coverage.iter() {
+        if let Some(shift) = coverage.shift.as_ref()
+            .filter(|shift| calendar.shift_index(shift).is_none())
+        {
+            report.push(
+                Severity::Error,
+                None,
+                format!(
+                    "The coverage of \"{}\" is for an unknown shift \"{}\"",
+                    coverage.role,
+                    shift,
+                ),
+            );
+        }
+
+        let with_role = reader.elements.iter()
+            .filter(|elem| elem.has_role(&coverage.role))
+            .count();
+
+        if with_role < coverage.min {
+            report.push(
+                Severity::Warning,
+                None,
+                format!(
+                    "{} elements have the role \"{}\", but {} are needed",
+                    with_role,
+                    coverage.role,
+                    coverage.min,
+                ),
+            );
+        }
+    }
+
+    for (i, pairing) in reader.pairings.iter().enumerate() {
+        for name in [&pairing.first, &pairing.second] {
+            if !rows_by_name.contains_key(name.as_str()) {
+                report.push(
+                    Severity::Warning,
+                    None,
+                    format!(
+                        "The pair on row {} of the \"Pairs\" sheet has \"{}\", \
+                            who isn't in the file",
+                        pairing.row,
+                        name,
+                    ),
+                );
+            }
+        }
+
+        if pairing.first == pairing.second {
+            report.push(
+                Severity::Error,
+                None,
+                format!(
+                    "The pair on row {} of the \"Pairs\" sheet is \"{}\" twice",
+                    pairing.row,
+                    pairing.first,
+                ),
+            );
+        }
+
+        let contradicted = reader.pairings[..i].iter().find(|other| {
+            other.rule != pairing.rule
+                && ((other.first == pairing.first && other.second == pairing.second)
+                    || (other.first == pairing.second
+                        && other.second == pairing.first))
+        });
+
+        if let Some(other) = contradicted {
+            report.push(
+                Severity::Error,
+                None,
+                format!(
+                    "\"{}\" and \"{}\" are both {} (row {}) and {} (row {}) \
+                        in the \"Pairs\" sheet",
+                    pairing.first,
+                    pairing.second,
+                    other.rule.as_str().to_lowercase(),
+                    other.row,
+                    pairing.rule.as_str().to_lowercase(),
+                    pairing.row,
+                ),
+            );
+        }
+    }
+
+    for leave in reader.leave.iter() {
+        if !rows_by_name.contains_key(leave.name.as_str()) {
+            report.push(
+                Severity::Warning,
+                None,
+                format!(
+                    "Leave on row {} of the \"Leave\" sheet is for \"{}\", \
+                        who isn't in the file",
+                    leave.row,
+                    leave.name,
+                ),
+            );
+        }
+    }
+
+    if reader.elements.len() < calendar.max_headcount() {
+        report.push(
+            Severity::Warning,
+            None,
+            format!(
+                "There are {} elements, but some days need {}",
+                reader.elements.len(),
+                calendar.max_headcount(),
+            ),
+        );
+    } else {
+        for day in working_days.iter() {
+            let win_len = calendar.total_headcount(*day);
+            let available = reader.elements.iter()
+                .filter(|elem| !elem.avoid_days.contains(day))
+                .count();
+
+            if available < win_len {
+                report.push(
+                    Severity::Warning,
+                    None,
+                    format!(
+                        "Only {} elements can be scheduled on {}, but it needs {}",
+                        available,
+                        day.as_str(),
+                        win_len,
+                    ),
+                );
+            }
+        }
+    }
+
+    report
+}
@@ -1,37 +1,319 @@
-use chrono::{DateTime, Datelike, Local};
+use chrono::{ Datelike, NaiveDate, TimeDelta };
 
-use crate::reader::ScheduledElement;
+use crate::{
+    calendar::Calendar, coverage::Coverage, ledger::{ Ledger, LedgerEntry },
+    pairing::{ Pairing, PairRule }, reader::ScheduledElement,
+    schedule::{ Assignment, Schedule, ScheduleWarning },
+    scheduler::Scheduler, shift::Shift,
+    trace::{ Action, Reason, Trace, TraceEntry }, types::Day,
+};
 
-use std::collections::HashSet;
-use rand::{ rng, seq::SliceRandom };
+use std::{ cell::RefCell, cmp::Reverse, iter };
+use rand::{ rng, rngs::StdRng, seq::SliceRandom, Rng, SeedableRng };
 
-/// Whether scheduling this element should be avoided.
-fn avoid(elem: &ScheduledElement, index: u32) -> bool {
-    elem.avoid_days.iter()
-        .filter(|x| x.to_u32() == index)
-        .count() > 0
+/// The most days an element can get ahead of the others because of its
+/// preferred days, at the full preference weight.
+const MAX_PREFERENCE_LEAD: f32 = 4.0;
+
+/// Rules the scheduler follows for a single run.
+#[derive(Debug, Clone)]
+pub struct Rules {
+    /// How strongly the preferred days are weighted against distributing the
+    /// days evenly, from `0.0` (preferences are ignored) to `1.0`.
+    pub preference_weight: f32,
+
+    /// Seed for shuffling the elements. The same seed, elements and calendar
+    /// always give the same schedule. A random seed is picked if it's `None`.
+    pub seed: Option<u64>,
+
+    /// Debts and rotation carried over from the previous period, e.g. from
+    /// [`Schedule::ledger`] of last month's schedule
+    pub carry_over: Ledger,
+
+    /// The most days in a row an element can be scheduled on, if there's a
+    /// limit
+    pub max_consecutive_days: Option<usize>,
+
+    /// The fewest days off an element gets after it's been scheduled on
+    /// [`Rules::max_consecutive_days`] in a row
+    pub min_days_off: usize,
+
+    /// The fewest hours between the end of an element's shift and the start
+    /// of its next one, e.g. between a night shift and the next morning
+    pub min_rest_hours: Option<usize>,
+
+    /// The elements that are scheduled together or never together
+    pub pairings: Vec<Pairing>,
+
+    /// Whether to record the decisions of the rotation in
+    /// [`Schedule::trace`]
+    pub trace: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            preference_weight: 0.5,
+            seed: None,
+            carry_over: Ledger::default(),
+            max_consecutive_days: None,
+            min_days_off: 0,
+            min_rest_hours: None,
+            pairings: Vec::new(),
+            trace: false,
+        }
+    }
+}
+
+/// A shift of a date that's being scheduled.
+struct Slot<'a> {
+    date: NaiveDate,
+
+    /// The day of the week, as in [`Day::to_u32`]
+    day: u32,
+    shift: &'a Shift,
+
+    /// Elements already scheduled on an earlier shift of the date
+    taken: &'a [usize],
+
+    /// The number of dates each element is scheduled on so far
+    counts: &'a [usize],
+
+    /// The dates and shifts each element is scheduled on so far
+    worked: &'a [Vec<(NaiveDate, usize)>],
+    shift_index: usize,
+    calendar: &'a Calendar,
+    rules: &'a Rules,
+
+    /// The other elements of the pairs each element is in, with their rule
+    partners: &'a [Vec<(usize, PairRule)>],
+
+    /// The decisions made so far, if they're traced
+    trace: Option<&'a RefCell<Vec<Step>>>,
+}
+
+/// A decision of the rotation, with the element by its place in the order.
+struct Step {
+    date: NaiveDate,
+    shift: usize,
+    pos: usize,
+    action: Action,
+    reason: Reason,
+    debt: i32,
+}
+
+impl Slot<'_> {
+    /// Records a decision about the element at `index`, if it's traced.
+    fn record(&self, index: usize, action: Action, reason: Reason, debt: i32) {
+        if let Some(trace) = self.trace {
+            trace.borrow_mut().push(Step {
+                date: self.date,
+                shift: self.shift_index,
+                pos: index,
+                action,
+                reason,
+                debt,
+            });
+        }
+    }
+}
+
+/// Whether this element avoids the shift of the date, i.e. it avoids the day
+/// or the shift, or it's on leave.
+pub fn avoids(
+    elem: &ScheduledElement,
+    date: NaiveDate,
+    shift: &Shift,
+) -> bool {
+    let day = date.weekday().num_days_from_sunday();
+
+    elem.avoid_days.iter().any(|x| x.to_u32() == day)
+        || elem.avoid_shifts.iter().any(|name| shift.is_named(name))
+        || elem.leave.iter().any(|dates| dates.contains(date))
+}
+
+/// Whether scheduling this element should be avoided on the slot.
+fn avoid(elem: &ScheduledElement, slot: &Slot) -> bool {
+    avoids(elem, slot.date, slot.shift)
+}
+
+/// Whether the dates and shifts an element works on, in the order of their
+/// dates, follow the rules for consecutive days and rest.
+pub(crate) fn rests_enough(
+    worked: impl Iterator<Item = (NaiveDate, usize)>,
+    calendar: &Calendar,
+    rules: &Rules,
+) -> bool {
+    let mut previous: Option<(NaiveDate, usize)> = None;
+    let mut streak: usize = 0;
+
+    for (date, shift) in worked {
+        if let Some((previous_date, previous_shift)) = previous {
+            let days_off = (date - previous_date).num_days() - 1;
+
+            // A single shift each date
+            if days_off < 0 {
+                return false;
+            }
+
+            if days_off == 0 {
+                streak += 1;
+            } else if rules.max_consecutive_days
+                .is_some_and(|max| streak >= max
+                    && (days_off as usize) < rules.min_days_off)
+            {
+                return false;
+            } else {
+                streak = 1;
+            }
+
+            // The default shift is the whole day, it has no times to rest
+            // between.
+            let previous_shift = &calendar.shifts[previous_shift];
+            let next_shift = &calendar.shifts[shift];
+
+            if let Some(hours) = rules.min_rest_hours.filter(|_| {
+                !previous_shift.is_default() && !next_shift.is_default()
+            }) {
+                let mut end = previous_date.and_time(previous_shift.end);
+
+                if previous_shift.ends_next_day() {
+                    end += TimeDelta::days(1);
+                }
+
+                if date.and_time(next_shift.start) - end
+                    < TimeDelta::hours(hours as i64)
+                {
+                    return false;
+                }
+            }
+        } else {
+            streak = 1;
+        }
+
+        if rules.max_consecutive_days.is_some_and(|max| streak > max) {
+            return false;
+        }
+
+        previous = Some((date, shift));
+    }
+
+    true
+}
+
+/// Whether the element at `index` can't be scheduled on the slot, i.e. it
+/// avoids it or it has to rest.
+fn blocked(elem: &ScheduledElement, index: usize, slot: &Slot) -> bool {
+    avoid(elem, slot)
+        || !rests_enough(
+            slot.worked[index].iter()
+                .copied()
+                .chain(iter::once((slot.date, slot.shift_index))),
+            slot.calendar,
+            slot.rules,
+        )
+}
+
+/// Gets the rule that keeps the element at `index` off the slot, for the
+/// trace.
+fn skip_reason(
+    elems: &[ScheduledElement],
+    index: usize,
+    slot: &Slot,
+) -> Reason {
+    let elem = &elems[index];
+
+    if elem.leave.iter().any(|dates| dates.contains(slot.date)) {
+        Reason::OnLeave
+    } else if elem.avoid_days.iter().any(|x| x.to_u32() == slot.day) {
+        Reason::AvoidsDay
+    } else if avoid(elem, slot) {
+        Reason::AvoidsShift
+    } else if blocked(elem, index, slot) {
+        Reason::Resting
+    } else {
+        Reason::KeptApart
+    }
+}
+
+/// Whether the pairing rules keep the element at `index` off the slot, i.e.
+/// it's never scheduled with an element that's on the date already, or it's
+/// always scheduled with one that can't be.
+fn kept_apart(
+    elems: &[ScheduledElement],
+    index: usize,
+    slot: &Slot,
+    scheduled: &[usize],
+) -> bool {
+    slot.partners[index].iter().any(|(partner, rule)| match rule {
+        PairRule::Apart => {
+            scheduled.contains(partner) || slot.taken.contains(partner)
+        }
+
+        PairRule::Together => !slot.taken.contains(partner)
+            && (blocked(&elems[*partner], *partner, slot)
+                || unavailable(&elems[*partner], *partner, slot)),
+    })
+}
+
+/// Whether the element at `index` can't be picked for the slot, without being
+/// owed a day for it, i.e. it's on an earlier shift of the date or it's
+/// reached its maximum.
+fn unavailable(elem: &ScheduledElement, index: usize, slot: &Slot) -> bool {
+    slot.taken.contains(&index)
+        || elem.max_assignments.is_some_and(|max| slot.counts[index] >= max)
+}
+
+/// Whether this element prefers to be scheduled on the day of the date or on
+/// the shift.
+pub fn prefers(
+    elem: &ScheduledElement,
+    date: NaiveDate,
+    shift: &Shift,
+) -> bool {
+    let day = date.weekday().num_days_from_sunday();
+
+    elem.prefer_days.iter().any(|x| x.to_u32() == day)
+        || elem.prefer_shifts.iter().any(|name| shift.is_named(name))
+}
+
+/// Whether this element prefers to be scheduled on the slot.
+fn prefer(elem: &ScheduledElement, slot: &Slot) -> bool {
+    prefers(elem, slot.date, slot.shift)
 }
 
 /// Gets the index of the element with most debt that cannot be avoided for the
-/// given day.
+/// given slot, and isn't already scheduled on its date.
 ///
-/// Negative debt need not be considered here.
+/// Negative debt need not be considered here. When preferences are weighted,
+/// the elements that prefer the day win the ties.
 fn max_debt_index(
-    elems: &Vec<ScheduledElement>,
-    debts: &Vec<i8>,
-    day_of_week: u32
+    elems: &[ScheduledElement],
+    debts: &[i32],
+    slot: &Slot,
+    scheduled: &[usize],
 ) -> Option<usize> {
-    let mut max_debt: i8 = 0;
+    let mut max_debt: i32 = 0;
     let mut max_debt_index = 0;
     let mut max_debt_found = false;
 
     // Try finding the max debt
     for (i, debt) in debts.iter().enumerate() {
-        if *debt == 0 {
+        if *debt == 0
+            || blocked(&elems[i], i, slot)
+            || scheduled.contains(&i)
+            || unavailable(&elems[i], i, slot)
+            || kept_apart(elems, i, slot, scheduled)
+        {
             continue;
         }
 
-        if *debt > max_debt && !avoid(&elems[i], day_of_week) {
+        let wins_tie = *debt == max_debt
+            && slot.rules.preference_weight > 0.0
+            && prefer(&elems[i], slot)
+            && !prefer(&elems[max_debt_index], slot);
+
+        if *debt > max_debt || wins_tie {
             max_debt = *debt;
             max_debt_index = i;
             max_debt_found = true;
@@ -46,135 +328,903 @@ fn max_debt_index(
     Some(max_debt_index)
 }
 
-/// Processes the scheduling for the month.
+/// Swaps the elements that prefer the day or shift into the slot's schedule,
+/// in place of the elements that don't.
 ///
-/// Params:
-/// - `elements` The elements to schedule
-/// - `month` The month of schedule
-/// - `win_len` The "Window Length"
-pub fn process(
-    elements: &Vec<ScheduledElement>,
-    month: DateTime<Local>,
+/// The number of swaps grows with the preference weight. An element swapped
+/// out is owed a day and one swapped in owes a day, so the debts still even
+/// out over the month. The elements a role of the slot needs aren't swapped
+/// out.
+fn swap_in_preferred(
+    elems: &[ScheduledElement],
+    debts: &mut [i32],
+    slot: &Slot,
+    scheduled: &mut [usize],
+) {
+    let weight = slot.rules.preference_weight.clamp(0.0, 1.0);
+    let max_lead = (weight * MAX_PREFERENCE_LEAD).round() as i32;
+
+    // Elements that prefer the day and aren't too far ahead already, the ones
+    // with the most debt first.
+    let mut swap_in: Vec<usize> = (0..elems.len())
+        .filter(|i| !scheduled.contains(i)
+            && !unavailable(&elems[*i], *i, slot)
+            && prefer(&elems[*i], slot)
+            && !blocked(&elems[*i], *i, slot)
+            && !kept_apart(elems, *i, slot, scheduled)
+            && debts[*i] > -max_lead)
+        .collect();
+
+    swap_in.sort_by_key(|i| Reverse(debts[*i]));
+
+    // Positions of the scheduled elements that don't prefer the day, the ones
+    // with the least debt first.
+    let mut swap_out: Vec<usize> = (0..scheduled.len())
+        .filter(|pos| !prefer(&elems[scheduled[*pos]], slot))
+        .collect();
+
+    swap_out.sort_by_key(|pos| debts[scheduled[*pos]]);
+
+    let swaps = ((weight * swap_out.len() as f32).round() as usize)
+        .min(swap_in.len());
+    let mut swap_in = swap_in.into_iter();
+
+    for pos in swap_out.into_iter().take(swaps) {
+        // The roles of the slot were filled first, and stay filled
+        if needed_for_roles(elems, slot, scheduled, scheduled[pos]) {
+            continue;
+        }
+
+        // An element swapped in earlier can keep this one apart
+        let Some(i) = swap_in.find(|i| !kept_apart(elems, *i, slot, scheduled))
+        else {
+            break;
+        };
+
+        debts[scheduled[pos]] += 1;
+        debts[i] -= 1;
+        slot.record(
+            scheduled[pos], Action::Removed, Reason::Preferred,
+            debts[scheduled[pos]],
+        );
+        slot.record(i, Action::Picked, Reason::Preferred, debts[i]);
+        scheduled[pos] = i;
+    }
+}
+
+/// Gets the coverage rules of the slot.
+fn needed_roles<'a>(slot: &Slot<'a>) -> Vec<&'a Coverage> {
+    let day = Day::from_u32(slot.day);
+
+    slot.calendar.coverage.iter()
+        .filter(|coverage| coverage.applies_to(day, slot.shift))
+        .collect()
+}
+
+/// Gets the number of scheduled elements with the role of the rule.
+fn covered(
+    elems: &[ScheduledElement],
+    scheduled: &[usize],
+    coverage: &Coverage,
+) -> usize {
+    scheduled.iter()
+        .filter(|i| elems[**i].has_role(&coverage.role))
+        .count()
+}
+
+/// Whether a coverage rule of the slot isn't met without the scheduled element
+/// at `index`.
+fn needed_for_roles(
+    elems: &[ScheduledElement],
+    slot: &Slot,
+    scheduled: &[usize],
+    index: usize,
+) -> bool {
+    needed_roles(slot).iter().any(|coverage| {
+        elems[index].has_role(&coverage.role)
+            && covered(elems, scheduled, coverage) <= coverage.min
+    })
+}
+
+/// Gets the coverage rules of the slot that aren't met, with the number of
+/// elements that cover them.
+fn uncovered<'a>(
+    elems: &[ScheduledElement],
+    slot: &Slot<'a>,
+    scheduled: &[usize],
+) -> Vec<(&'a Coverage, usize)> {
+    needed_roles(slot).into_iter()
+        .map(|coverage| (coverage, covered(elems, scheduled, coverage)))
+        .filter(|(coverage, count)| *count < coverage.min)
+        .collect()
+}
+
+/// Schedules the elements with the roles the slot needs, before the rest of
+/// its places are filled.
+///
+/// The ones with the most debt are picked first, then the ones closest after
+/// the start of the window. One picked outside the first `window` places after
+/// `start` takes a place of the window, so it owes a day for it.
+fn reserve_roles(
+    elems: &[ScheduledElement],
+    debts: &mut [i32],
+    slot: &Slot,
+    scheduled: &mut Vec<usize>,
+    win_len: usize,
+    start: usize,
+    window: usize,
+) {
+    let distance = |i: usize| (i + elems.len() - start % elems.len()) % elems.len();
+
+    for coverage in needed_roles(slot) {
+        while covered(elems, scheduled, coverage) < coverage.min
+            && scheduled.len() < win_len
+        {
+            let candidate = (0..elems.len())
+                .filter(|i| !scheduled.contains(i)
+                    && elems[*i].has_role(&coverage.role)
+                    && !unavailable(&elems[*i], *i, slot)
+                    && !blocked(&elems[*i], *i, slot)
+                    && !kept_apart(elems, *i, slot, scheduled))
+                .max_by_key(|i| (debts[*i], Reverse(distance(*i))));
+
+            let Some(i) = candidate else {
+                break;
+            };
+
+            scheduled.push(i);
+
+            if distance(i) >= window {
+                debts[i] -= 1;
+            }
+
+            slot.record(i, Action::Picked, Reason::Role, debts[i]);
+        }
+    }
+}
+
+/// Schedules the partners of the elements that are always scheduled together,
+/// in the empty places of the slot or in place of the elements without
+/// partners.
+///
+/// The elements whose partners can't be scheduled are taken off the slot and
+/// owed a day for it, like an avoided day.
+fn pair_up(
+    elems: &[ScheduledElement],
+    debts: &mut [i32],
+    slot: &Slot,
+    scheduled: &mut Vec<usize>,
     win_len: usize,
-) -> Vec<Vec<Vec<String>>> {
-    let mut month_vec: Vec<Vec<Vec<String>>> = Vec::with_capacity(5);
+) {
+    let has_partner = |index: usize| slot.partners[index].iter()
+        .any(|(_, rule)| *rule == PairRule::Together);
+
+    // Elements taken off the slot, so that their partners aren't paired with
+    // them again
+    let mut dropped: Vec<usize> = Vec::new();
+    let mut i = 0;
 
-    // randomize the list of elements
-    let mut elem_random = elements.clone();
-    elem_random.shuffle(&mut rng());
+    while i < scheduled.len() {
+        let index = scheduled[i];
+        let missing: Vec<usize> = slot.partners[index].iter()
+            .filter(|(partner, rule)| *rule == PairRule::Together
+                && !scheduled.contains(partner)
+                && !slot.taken.contains(partner))
+            .map(|(partner, _)| *partner)
+            .collect();
+
+        let mut paired = true;
+
+        for partner in missing {
+            if dropped.contains(&partner)
+                || unavailable(&elems[partner], partner, slot)
+                || blocked(&elems[partner], partner, slot)
+                || kept_apart(elems, partner, slot, scheduled)
+            {
+                paired = false;
+                break;
+            }
+
+            if scheduled.len() < win_len {
+                scheduled.push(partner);
+                debts[partner] -= 1;
+                slot.record(partner, Action::Picked, Reason::Pair, debts[partner]);
+                continue;
+            }
+
+            let swap_out = (0..scheduled.len())
+                .filter(|pos| !has_partner(scheduled[*pos])
+                    && !needed_for_roles(elems, slot, scheduled, scheduled[*pos]))
+                .min_by_key(|pos| debts[scheduled[*pos]]);
+
+            let Some(pos) = swap_out else {
+                paired = false;
+                break;
+            };
+
+            debts[scheduled[pos]] += 1;
+            debts[partner] -= 1;
+            slot.record(
+                scheduled[pos], Action::Removed, Reason::Pair,
+                debts[scheduled[pos]],
+            );
+            slot.record(partner, Action::Picked, Reason::Pair, debts[partner]);
+            scheduled[pos] = partner;
+        }
+
+        if paired {
+            i += 1;
+        } else {
+            scheduled.remove(i);
+            debts[index] += 1;
+            slot.record(index, Action::Removed, Reason::KeptApart, debts[index]);
+            dropped.push(index);
+        }
+    }
+}
 
-    let m: u32 = month.month();
+/// Fills the empty places of the slot with the elements that have no partner
+/// to bring along, the ones with the most debt first, then the ones closest
+/// after `start`.
+fn refill(
+    elems: &[ScheduledElement],
+    debts: &mut [i32],
+    slot: &Slot,
+    scheduled: &mut Vec<usize>,
+    win_len: usize,
+    start: usize,
+) {
+    while scheduled.len() < win_len {
+        let candidate = (0..elems.len())
+            .filter(|i| !scheduled.contains(i)
+                && !unavailable(&elems[*i], *i, slot)
+                && !blocked(&elems[*i], *i, slot)
+                && !kept_apart(elems, *i, slot, scheduled)
+                && slot.partners[*i].iter().all(|(partner, rule)| {
+                    *rule == PairRule::Apart || scheduled.contains(partner)
+                        || slot.taken.contains(partner)
+                }))
+            .max_by_key(|i| (
+                debts[*i],
+                Reverse((*i + elems.len() - start % elems.len()) % elems.len()),
+            ));
 
-    println!("current month is: {0}", m);
+        let Some(i) = candidate else {
+            break;
+        };
 
-    let first_day = month.with_day(1).unwrap();
+        scheduled.push(i);
+        debts[i] -= 1;
+        slot.record(i, Action::Picked, Reason::Refill, debts[i]);
+    }
+}
 
-    let start_day_index = first_day.weekday().number_from_sunday() - 1;
+/// Gives the elements that are below their minimum the assignments of the
+/// elements furthest above theirs, or the empty places of the shifts they can
+/// work on.
+///
+/// The elements whose minimum still can't be met are added to the warnings of
+/// the schedule.
+///
+/// Params:
+/// - `schedule` The schedule with every date scheduled
+/// - `debts` The debts of the elements, in the order they were shuffled in
+/// - `order` The ids of the elements, in the order they were shuffled in
+/// - `rules` The rules for this run, for the rest between the days
+/// - `partners` The pairs of each element, by its id
+/// - `trace` The decisions made so far, if they're traced
+fn meet_minimums(
+    schedule: &mut Schedule,
+    debts: &mut [i32],
+    order: &[usize],
+    rules: &Rules,
+    partners: &[Vec<(usize, PairRule)>],
+    trace: Option<&RefCell<Vec<Step>>>,
+) {
+    let elements = &schedule.elements;
+    let calendar = &schedule.calendar;
 
-    println!("Start day index: {0}", start_day_index);
+    let mut pos_of = vec![0; elements.len()];
 
-    // The number of days that have been scheduled
-    let mut total_day_count: u8 = 0;
-    let mut window_offset: u32 = 0;
+    for (pos, id) in order.iter().enumerate() {
+        pos_of[*id] = pos;
+    }
 
-    let mut debts: Vec<i8> = Vec::with_capacity(elem_random.len());
+    let mut counts = vec![0; elements.len()];
 
-    for _ in 0..elem_random.len() {
-        debts.push(0);
+    for assignment in schedule.assignments.iter() {
+        counts[assignment.element_id] += 1;
     }
 
-    // Set containing each day's schedule
-    let mut day_set: HashSet<String> = HashSet::with_capacity(7);
+    // The elements scheduled together can't be moved on their own
+    let has_partner = |id: usize| partners[id].iter()
+        .any(|(_, rule)| *rule == PairRule::Together);
+
+    // Assignments an element can give away without going below its minimum
+    let surplus = |counts: &[usize], id: usize| counts[id]
+        .saturating_sub(elements[id].min_assignments.unwrap_or_default());
+
+    let mut needy: Vec<usize> = (0..elements.len())
+        .filter(|id| elements[*id].min_assignments
+            .is_some_and(|min| counts[*id] < min))
+        .filter(|id| !has_partner(*id))
+        .collect();
+
+    needy.sort_by_key(|id| Reverse(
+        elements[*id].min_assignments.unwrap_or_default() - counts[*id]
+    ));
+
+    let mut added = Vec::new();
+    let record = |date, shift, pos, action, debt| {
+        if let Some(trace) = trace {
+            trace.borrow_mut().push(Step {
+                date,
+                shift,
+                pos,
+                action,
+                reason: Reason::Minimum,
+                debt,
+            });
+        }
+    };
+
+    for id in needy {
+        let min = elements[id].min_assignments.unwrap_or_default();
+
+        // The shifts the element can work on, as `(date, shift)`
+        let open_slots: Vec<(NaiveDate, usize)> = calendar.dates()
+            .filter(|date| !schedule.assignments.iter().chain(added.iter())
+                .any(|a: &Assignment| a.date == *date && (a.element_id == id
+                    || partners[id].contains(&(a.element_id, PairRule::Apart)))))
+            .flat_map(|date| (0..calendar.shifts.len())
+                .map(move |shift| (date, shift)))
+            .filter(|(date, shift)| !avoid(&elements[id], &Slot {
+                date: *date,
+                day: date.weekday().num_days_from_sunday(),
+                shift: &calendar.shifts[*shift],
+                taken: &[],
+                counts: &[],
+                worked: &[],
+                shift_index: *shift,
+                calendar,
+                rules,
+                partners: &[],
+                trace: None,
+            }))
+            .collect();
+
+        let mut used_dates: Vec<NaiveDate> = Vec::new();
 
-    for i in 0..5 {
-        let mut week_vec: Vec<Vec<String>> = Vec::with_capacity(7);
+        // Empty places first, then the assignments of the element with the
+        // most to spare
+        for (date, shift) in open_slots {
+            if counts[id] >= min
+                || elements[id].max_assignments.is_some_and(|max| counts[id] >= max)
+            {
+                break;
+            }
 
-        for j in 0..7 {
-            if i == 0 && j < start_day_index {
-                println!("<1> skipped: i={0}, j={1}", i, j);
-                week_vec.push(Vec::<String>::new());
+            if used_dates.contains(&date) {
                 continue;
             }
 
-            if j == 0 || j == 6 {
-                println!("skipped: i={0}, j={1}", i, j);
-                total_day_count += 1;
-                week_vec.push(Vec::<String>::new());
+            let mut worked: Vec<(NaiveDate, usize)> = schedule.assignments
+                .iter()
+                .chain(added.iter())
+                .filter(|a| a.element_id == id)
+                .map(|a| (a.date, a.shift))
+                .chain(iter::once((date, shift)))
+                .collect();
+
+            worked.sort();
+
+            if !rests_enough(worked.into_iter(), calendar, rules) {
                 continue;
             }
 
-            if elem_random.len() < win_len {
-                week_vec.push(
-                    elem_random.iter()
-                        .filter(|elem| avoid(elem, j))
-                        .map(|elem| elem.text.clone())
-                        .collect()
-                );
-            } else {
-                let mut index: usize = 0;
+            let day = Day::from_u32(date.weekday().num_days_from_sunday());
+            let filled = schedule.assignments.iter().chain(added.iter())
+                .filter(|a| a.date == date && a.shift == shift)
+                .count();
+
+            if calendar.is_working_day(day)
+                && calendar.holiday(date).is_none()
+                && filled < calendar.shift_headcount(day, &calendar.shifts[shift])
+            {
+                added.push(Assignment { date, element_id: id, shift });
+                used_dates.push(date);
+                counts[id] += 1;
+                debts[pos_of[id]] -= 1;
+                record(date, shift, pos_of[id], Action::Picked, debts[pos_of[id]]);
+                continue;
+            }
+
+            // The roles of the slot have to stay covered without the donor
+            let in_slot: Vec<usize> = schedule.assignments.iter()
+                .filter(|a| a.date == date && a.shift == shift)
+                .map(|a| a.element_id)
+                .collect();
+
+            let keeps_coverage = |donor: usize| calendar.coverage.iter()
+                .filter(|coverage| coverage.applies_to(
+                    day, &calendar.shifts[shift]
+                ))
+                .all(|coverage| !elements[donor].has_role(&coverage.role)
+                    || elements[id].has_role(&coverage.role)
+                    || in_slot.iter()
+                        .filter(|i| elements[**i].has_role(&coverage.role))
+                        .count() > coverage.min);
+
+            let donor = schedule.assignments.iter_mut()
+                .filter(|a| a.date == date && a.shift == shift)
+                .filter(|a| surplus(&counts, a.element_id) > 0)
+                .filter(|a| keeps_coverage(a.element_id))
+                .filter(|a| !has_partner(a.element_id))
+                .max_by_key(|a| surplus(&counts, a.element_id));
+
+            if let Some(assignment) = donor {
+                let donor_pos = pos_of[assignment.element_id];
+
+                counts[assignment.element_id] -= 1;
+                debts[donor_pos] += 1;
+                record(date, shift, donor_pos, Action::Removed, debts[donor_pos]);
+
+                assignment.element_id = id;
+                used_dates.push(date);
+                counts[id] += 1;
+                debts[pos_of[id]] -= 1;
+                record(date, shift, pos_of[id], Action::Picked, debts[pos_of[id]]);
+            }
+        }
+    }
+
+    schedule.assignments.extend(added);
+    schedule.assignments.sort_by_key(|a| (a.date, a.shift));
+
+    for (id, elem) in elements.iter().enumerate() {
+        if let Some(min) = elem.min_assignments.filter(|min| counts[id] < *min) {
+            schedule.warnings.push(ScheduleWarning::BelowMinimum {
+                element_id: id,
+                name: elem.text.clone(),
+                assigned: counts[id],
+                min,
+            });
+        }
+    }
+}
+
+/// Gets a warning for each shift of the schedule with fewer elements than its
+/// headcount, with the number of the other elements kept off it by each rule.
+///
+/// Params:
+/// - `schedule` The schedule with every date scheduled
+/// - `rules` The rules it was made with, for the rest and the pairs
+pub(crate) fn underfilled(
+    schedule: &Schedule,
+    rules: &Rules,
+) -> Vec<ScheduleWarning> {
+    let elements = &schedule.elements;
+    let calendar = &schedule.calendar;
+    let partners = partners(elements, &rules.pairings);
+    let mut warnings = Vec::new();
+
+    for date in calendar.dates() {
+        let day = Day::from_u32(date.weekday().num_days_from_sunday());
+
+        if !calendar.is_working_day(day) || calendar.holiday(date).is_some() {
+            continue;
+        }
+
+        let on_date: Vec<usize> = schedule.on_date(date)
+            .map(|a| a.element_id)
+            .collect();
+
+        for (index, shift) in calendar.shifts.iter().enumerate() {
+            let headcount = calendar.shift_headcount(day, shift);
+            let scheduled = schedule.on_date(date)
+                .filter(|a| a.shift == index)
+                .count();
 
-                // while day_vec.len() < win_len && !flag && index != window_offset {
-                while index < win_len {
-                    let abs_index = (index + window_offset as usize) % elem_random.len();
+            if scheduled >= headcount {
+                continue;
+            }
+
+            let on_shift = |id: usize| schedule.on_date(date)
+                .any(|a| a.element_id == id && a.shift == index);
+
+            // The hard rule that keeps the element off the shift, if there's
+            // one
+            let rule_broken = |id: usize| {
+                let elem = &elements[id];
+                let mut worked: Vec<(NaiveDate, usize)> = schedule.by_element(id)
+                    .map(|a| (a.date, a.shift))
+                    .chain(iter::once((date, index)))
+                    .collect();
+                worked.sort();
+
+                if on_date.contains(&id)
+                    || elem.max_assignments
+                        .is_some_and(|max| worked.len() > max)
+                {
+                    Some(Reason::Unavailable)
+                } else if elem.leave.iter().any(|dates| dates.contains(date)) {
+                    Some(Reason::OnLeave)
+                } else if elem.avoid_days.contains(&day) {
+                    Some(Reason::AvoidsDay)
+                } else if avoids(elem, date, shift) {
+                    Some(Reason::AvoidsShift)
+                } else if !rests_enough(worked.into_iter(), calendar, rules) {
+                    Some(Reason::Resting)
+                } else {
+                    None
+                }
+            };
+
+            let mut reasons: Vec<(Reason, usize)> = Vec::new();
 
-                    if avoid(&elem_random[abs_index], j) {
-                        debts[abs_index] += 1;
+            for (id, pairs) in partners.iter().enumerate() {
+                if on_shift(id) {
+                    continue;
+                }
+
+                // An element is kept apart by the partner it can't work
+                // with, or by the one it works with when that one can't
+                let reason = rule_broken(id).unwrap_or_else(|| {
+                    if pairs.iter().any(|(partner, rule)| match rule {
+                        PairRule::Apart => on_date.contains(partner),
+                        PairRule::Together => !on_shift(*partner)
+                            && rule_broken(*partner).is_some(),
+                    }) {
+                        Reason::KeptApart
                     } else {
-                        day_set.insert(elem_random[abs_index].text.clone());
+                        Reason::Unpicked
                     }
+                });
 
-                    index = index + 1;
+                match reasons.iter_mut().find(|(other, _)| *other == reason) {
+                    Some((_, count)) => *count += 1,
+                    None => reasons.push((reason, 1)),
                 }
+            }
+
+            warnings.push(ScheduleWarning::Underfilled {
+                date,
+                shift: (calendar.shifts.len() > 1).then(|| shift.name.clone()),
+                scheduled,
+                headcount,
+                reasons,
+            });
+        }
+    }
+
+    warnings
+}
 
-                while day_set.len() < win_len {
-                    match max_debt_index(&elem_random, &debts, j) {
-                        Some(max_debt) => {
-                            day_set.insert(elem_random[max_debt].text.clone());
-                            debts[max_debt] -= 1;
+/// Gets the other elements of the pairs each element is in, with their rule.
+///
+/// Pairs with names that aren't in `elements` are left out.
+pub(crate) fn partners(
+    elements: &[ScheduledElement],
+    pairings: &[Pairing],
+) -> Vec<Vec<(usize, PairRule)>> {
+    let mut partners = vec![Vec::new(); elements.len()];
+    let id_of = |name: &str| elements.iter()
+        .position(|elem| elem.text.trim() == name);
+
+    for pairing in pairings.iter() {
+        if let (Some(first), Some(second)) = (
+            id_of(&pairing.first), id_of(&pairing.second)
+        ) {
+            if first != second {
+                partners[first].push((second, pairing.rule));
+                partners[second].push((first, pairing.rule));
+            }
+        }
+    }
+
+    partners
+}
+
+/// Processes the scheduling for the month with the [`Rotation`].
+///
+/// Params:
+/// - `elements` The elements to schedule
+/// - `calendar` The month of schedule, along with the "Window Length" of each
+///   day of the week
+/// - `rules` The rules for this run, e.g. how strongly preferred days weigh
+///
+/// Returns the schedule with the elements assigned to each scheduled date.
+pub fn process(
+    elements: &[ScheduledElement],
+    calendar: &Calendar,
+    rules: &Rules,
+) -> Schedule {
+    Rotation.schedule(elements, calendar, rules)
+}
+
+/// Schedules the elements in a rotation. The elements are shuffled, and a
+/// window moves along them each day. The elements the window has to skip are
+/// owed days, and the ones with the most debt fill the empty places.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Rotation;
+
+impl Scheduler for Rotation {
+    fn name(&self) -> &str { "Fast rotation" }
+
+    fn schedule(
+        &self,
+        elements: &[ScheduledElement],
+        calendar: &Calendar,
+        rules: &Rules,
+    ) -> Schedule {
+        let seed = rules.seed.unwrap_or_else(|| rng().random());
+        let mut schedule = Schedule::new(calendar, elements, seed);
+
+        // randomize the list of elements, `order` maps them back to their ids
+        let mut order: Vec<usize> = (0..elements.len()).collect();
+        order.shuffle(&mut StdRng::seed_from_u64(seed));
+
+        // Continue the rotation of the previous period, with the new elements
+        // at the end.
+        if !rules.carry_over.is_empty() {
+            order.sort_by_key(|id| {
+                rules.carry_over.rotation(&elements[*id].text).unwrap_or(usize::MAX)
+            });
+        }
+
+        let elem_random: Vec<ScheduledElement> = order.iter()
+            .map(|id| elements[*id].clone())
+            .collect();
+
+        // The pairs of each element by its id, and by its place in the order
+        let partners_by_id = partners(elements, &rules.pairings);
+        let partners: Vec<Vec<(usize, PairRule)>> = order.iter()
+            .map(|id| partners_by_id[*id].iter()
+                .map(|(partner, rule)| (
+                    order.iter().position(|other| other == partner)
+                        .expect("every element is in the order"),
+                    *rule,
+                ))
+                .collect())
+            .collect();
+
+        let mut window_offset: u32 = 0;
+
+        let mut debts: Vec<i32> = elem_random.iter()
+            .map(|elem| rules.carry_over.debt(&elem.text))
+            .collect();
+
+        // Indices of the elements in each shift's schedule
+        let mut day_vec: Vec<usize> = Vec::with_capacity(calendar.max_headcount());
+
+        // Indices of the elements scheduled on any shift of the date
+        let mut taken: Vec<usize> = Vec::with_capacity(calendar.max_headcount());
+
+        // Number of dates each element is scheduled on
+        let mut counts: Vec<usize> = vec![0; elem_random.len()];
+
+        // Dates and shifts each element is scheduled on
+        let mut worked: Vec<Vec<(NaiveDate, usize)>> = vec![
+            Vec::new(); elem_random.len()
+        ];
+
+        let trace = RefCell::new(Vec::new());
+        let traced = rules.trace.then_some(&trace);
+
+        for date in calendar.dates() {
+            let j = date.weekday().num_days_from_sunday();
+
+            if !calendar.is_working_day(Day::from_u32(j))
+                || calendar.holiday(date).is_some()
+            {
+                continue;
+            }
+
+            // Each shift's window starts where the previous one's ended, so that
+            // the shifts of a date don't overlap.
+            let mut shift_offset: usize = 0;
+
+            for (shift_index, shift) in calendar.shifts.iter().enumerate() {
+                let win_len = calendar.shift_headcount(Day::from_u32(j), shift);
+                let slot = Slot {
+                    date,
+                    day: j,
+                    shift,
+                    taken: &taken,
+                    counts: &counts,
+                    worked: &worked,
+                    shift_index,
+                    calendar,
+                    rules,
+                    partners: &partners,
+                    trace: traced,
+                };
+
+                // Where the slot's window starts, for the places freed by the
+                // pairs
+                let mut window_start = 0;
+
+                if elem_random.len() - taken.len() < win_len {
+                    // Everyone is picked, so nobody owes a day for a role
+                    reserve_roles(
+                        &elem_random, &mut debts, &slot, &mut day_vec, win_len,
+                        0, elem_random.len(),
+                    );
+
+                    for (pos, elem) in elem_random.iter().enumerate() {
+                        if !day_vec.contains(&pos)
+                            && !blocked(elem, pos, &slot)
+                            && !unavailable(elem, pos, &slot)
+                            && !kept_apart(&elem_random, pos, &slot, &day_vec)
+                        {
+                            day_vec.push(pos);
+                            slot.record(
+                                pos, Action::Picked, Reason::Everyone, debts[pos],
+                            );
+                        }
+                    }
+                } else {
+                    window_start = window_offset as usize + shift_offset;
+                    reserve_roles(
+                        &elem_random, &mut debts, &slot, &mut day_vec, win_len,
+                        window_start, win_len,
+                    );
+
+                    let mut index: usize = 0;
+
+                    while index < win_len {
+                        let abs_index = (index + window_start) % elem_random.len();
+
+                        if day_vec.contains(&abs_index) {
+                            // Already picked for a role, on its own turn
+                        } else if unavailable(&elem_random[abs_index], abs_index, &slot) {
+                            // Not owed anything for the days it can't have
+                            slot.record(
+                                abs_index, Action::Skipped, Reason::Unavailable,
+                                debts[abs_index],
+                            );
+                        } else if blocked(&elem_random[abs_index], abs_index, &slot)
+                            || kept_apart(&elem_random, abs_index, &slot, &day_vec)
+                        {
+                            debts[abs_index] += 1;
+                            slot.record(
+                                abs_index,
+                                Action::Skipped,
+                                skip_reason(&elem_random, abs_index, &slot),
+                                debts[abs_index],
+                            );
+                        } else if day_vec.len() >= win_len {
+                            // Its place went to one picked for a role
+                            debts[abs_index] += 1;
+                            slot.record(
+                                abs_index, Action::Skipped, Reason::Role,
+                                debts[abs_index],
+                            );
+                        } else {
+                            day_vec.push(abs_index);
+                            slot.record(
+                                abs_index, Action::Picked, Reason::Rotation,
+                                debts[abs_index],
+                            );
                         }
 
-                        None => { break; }
+                        index += 1;
                     }
-                }
 
-                while day_set.len() < win_len
-                    && index != window_offset as usize
-                {
-                    let abs_index = (index + window_offset as usize) % elem_random.len();
+                    if rules.preference_weight > 0.0 {
+                        swap_in_preferred(
+                            &elem_random, &mut debts, &slot, &mut day_vec
+                        );
+                    }
+
+                    while day_vec.len() < win_len {
+                        match max_debt_index(
+                            &elem_random, &debts, &slot, &day_vec
+                        ) {
+                            Some(max_debt) => {
+                                day_vec.push(max_debt);
+                                debts[max_debt] -= 1;
+                                slot.record(
+                                    max_debt, Action::Picked, Reason::Debt,
+                                    debts[max_debt],
+                                );
+                            }
+
+                            None => { break; }
+                        }
+                    }
+
+                    // Fill the rest with the elements after the window, going
+                    // around the list once at most.
+                    while day_vec.len() < win_len && index < elem_random.len() {
+                        let abs_index = (index + window_start) % elem_random.len();
+
+                        if !blocked(&elem_random[abs_index], abs_index, &slot)
+                            && !day_vec.contains(&abs_index)
+                            && !unavailable(&elem_random[abs_index], abs_index, &slot)
+                            && !kept_apart(&elem_random, abs_index, &slot, &day_vec)
+                        {
+                            day_vec.push(abs_index);
+                            debts[abs_index] -= 1;
+                            slot.record(
+                                abs_index, Action::Picked, Reason::FillIn,
+                                debts[abs_index],
+                            );
+                        }
 
-                    if !avoid(&elem_random[abs_index], j) {
-                        day_set.insert(elem_random[abs_index].text.clone());
-                        debts[abs_index] -= 1;
+                        index += 1;
                     }
 
-                    index = index + 1;
+                    shift_offset += win_len;
                 }
 
-                window_offset = (window_offset + 1) % win_len as u32;
+                pair_up(&elem_random, &mut debts, &slot, &mut day_vec, win_len);
+                refill(
+                    &elem_random, &mut debts, &slot, &mut day_vec, win_len,
+                    window_start,
+                );
+
+                for (coverage, covered) in uncovered(&elem_random, &slot, &day_vec) {
+                    schedule.warnings.push(ScheduleWarning::RoleUncovered {
+                        date,
+                        shift: (calendar.shifts.len() > 1)
+                            .then(|| shift.name.clone()),
+                        role: coverage.role.clone(),
+                        covered,
+                        min: coverage.min,
+                    });
+                }
 
-                week_vec.push(day_set.drain().collect());
+                for pos in day_vec.drain(..) {
+                    schedule.assign(date, order[pos], shift_index);
+                    taken.push(pos);
+                    counts[pos] += 1;
+                    worked[pos].push((date, shift_index));
+                }
             }
 
-            total_day_count += 1;
+            taken.clear();
 
-            if total_day_count == month.num_days_in_month() {
-                break;
+            if !elem_random.is_empty() {
+                window_offset = (window_offset + 1) % elem_random.len() as u32;
             }
         }
 
-        month_vec.push(week_vec);
+        meet_minimums(
+            &mut schedule, &mut debts, &order, rules, &partners_by_id, traced,
+        );
 
-        if total_day_count == month.num_days_in_month() {
-            if month_vec.len() == 4 {
-                month_vec.push(Vec::<Vec<String>>::new());
-            }
+        let gaps = underfilled(&schedule, rules);
+        schedule.warnings.extend(gaps);
 
-            break;
-        }
+        schedule.trace = Trace {
+            entries: trace.into_inner().into_iter()
+                .map(|step| TraceEntry {
+                    date: step.date,
+                    shift: calendar.shifts[step.shift].name.clone(),
+                    element_id: order[step.pos],
+                    name: elem_random[step.pos].text.clone(),
+                    action: step.action,
+                    reason: step.reason,
+                    debt: step.debt,
+                })
+                .collect(),
+        };
 
-        println!("debts: {:?}", debts);
-    }
+        schedule.ledger = Ledger {
+            entries: elem_random.iter()
+                .enumerate()
+                .map(|(pos, elem)| LedgerEntry {
+                    name: elem.text.clone(),
+                    debt: debts[pos],
+                    rotation: Some(
+                        (pos + elem_random.len() - window_offset as usize)
+                            % elem_random.len()
+                    ),
+                })
+                .collect(),
+        };
 
-    month_vec
+        schedule
+    }
 }
-
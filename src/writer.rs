@@ -1,124 +1,189 @@
-use crate::types::{ FileExt, Day, Month };
+use crate::{
+    ledger::LEDGER_SHEET, schedule::Schedule,
+    stats::{ ScheduleStats, STATS_SHEET }, trace::TRACE_SHEET,
+    types::{ FileExt, Day },
+};
 
-use chrono::{ DateTime, Datelike, Local };
+use std::{ iter, path::Path };
+
+use chrono::{ Datelike, NaiveDate };
 use rust_xlsxwriter::*;
 use native_dialog::DialogBuilder;
 
-#[derive(Debug, Default, Clone)]
-pub struct Writer {
-    month: DateTime<Local>,
-    month_schedule: Vec<Vec<Vec<String>>>,
-    elem_num: usize,
+/// Formats of the cells of a single day in the calendar.
+///
+/// Each day is three columns wide, with the date and the elements in the
+/// middle of its border.
+struct DayFormats {
+    /// The name of the day of the week, above the first week
+    header: Format,
+    date_left: Format,
+    date_mid: Format,
+    date_right: Format,
+    element_left: Format,
+    element_mid: Format,
+    element_right: Format,
+
+    /// The name and times of a shift, above its elements
+    shift_mid: Format,
+    bottom_left: Format,
+    bottom_mid: Format,
+    bottom_right: Format,
 }
 
-impl Writer {
-    pub fn init(
-        &mut self,
-        elem_num: usize,
-        month: DateTime<Local>,
-        month_schedule: &Vec<Vec<Vec<String>>>
-    ) {
-        self.elem_num = elem_num;
-        self.month = month.clone();
-        self.month_schedule = month_schedule.clone();
-    }
-
-    pub fn export(&self, ext: FileExt) {
-        match ext {
-            FileExt::Xlsx => self.export_xlsx(),
-            FileExt::Csv => {}
-        }
-    }
-
-    fn export_xlsx(&self) {
-        let mut workbook = Workbook::new();
-
-        let white = Color::RGB(0xffffff);
-        let wknd_color = Color::RGB(0xfce4d6);
-
-        let date_format = Format::new()
+impl DayFormats {
+    fn new(background: Color) -> Self {
+        let date_left = Format::new()
             .set_bold()
-            .set_background_color(white)
+            .set_background_color(background)
             .set_border_top(FormatBorder::Thin)
             .set_border_left(FormatBorder::Thin);
 
-        let date_mid_format = date_format.clone()
+        let date_mid = date_left.clone()
             .set_border_left(FormatBorder::None);
 
-        let date_right_format = date_mid_format.clone()
+        let date_right = date_mid.clone()
             .set_border_right(FormatBorder::Thin);
 
-        let date_weekend_format = Format::new()
+        let header = Format::new()
+            .set_border(FormatBorder::Thin)
             .set_bold()
-            .set_background_color(wknd_color)
-            .set_border_top(FormatBorder::Thin)
+            .set_background_color(background)
+            .set_align(FormatAlign::Center);
+
+        let element_mid = Format::new()
+            .set_background_color(background);
+
+        let element_left = element_mid.clone()
             .set_border_left(FormatBorder::Thin);
 
-        let date_mid_weekend_format = date_weekend_format.clone()
+        let element_right = element_mid.clone()
+            .set_border_right(FormatBorder::Thin);
+
+        let shift_mid = element_mid.clone()
+            .set_italic();
+
+        let bottom_left = Format::new()
+            .set_background_color(background)
+            .set_border_left(FormatBorder::Thin)
+            .set_border_bottom(FormatBorder::Thin);
+
+        let bottom_mid = bottom_left.clone()
             .set_border_left(FormatBorder::None);
 
-        let date_right_weekend_format = date_mid_weekend_format.clone()
+        let bottom_right = bottom_mid.clone()
             .set_border_right(FormatBorder::Thin);
 
-        let merge_format = Format::new()
-            .set_border(FormatBorder::Thin)
-            .set_bold()
-            .set_background_color(white)
-            .set_align(FormatAlign::Center);
+        Self {
+            header,
+            date_left,
+            date_mid,
+            date_right,
+            element_left,
+            element_mid,
+            element_right,
+            shift_mid,
+            bottom_left,
+            bottom_mid,
+            bottom_right,
+        }
+    }
+}
 
-        let merge_weekend_format = Format::new()
-            .set_border(FormatBorder::Thin)
-            .set_bold()
-            .set_background_color(wknd_color)
-            .set_align(FormatAlign::Center);
+#[derive(Debug, Default, Clone)]
+pub struct Writer {
+    schedule: Schedule,
 
-        let element_mid_format = Format::new()
-            .set_background_color(white);
+    /// The number of element rows of each shift in a week
+    shift_rows: Vec<usize>,
+}
 
-        let element_left_format = element_mid_format.clone()
-            .set_border_left(FormatBorder::Thin);
+impl Writer {
+    pub fn init(&mut self, schedule: &Schedule) {
+        self.shift_rows = schedule.shifts.iter()
+            .map(|shift| schedule.calendar.max_shift_headcount(shift))
+            .collect();
 
-        let element_right_format = element_mid_format.clone()
-            .set_border_right(FormatBorder::Thin);
+        self.schedule = schedule.clone();
+    }
 
-        let element_mid_weekend_format = Format::new()
-            .set_background_color(wknd_color);
+    /// Whether each shift is labeled above its elements. A single shift
+    /// isn't, so that the days stay a plain list of elements.
+    fn shifts_labeled(&self) -> bool {
+        self.schedule.shifts.len() > 1
+    }
 
-        let element_left_weekend_format = element_mid_weekend_format.clone()
-            .set_border_left(FormatBorder::Thin);
+    /// Gets the number of rows below the dates in each week.
+    fn rows_per_week(&self) -> usize {
+        let labels = if self.shifts_labeled() {
+            self.shift_rows.len()
+        } else {
+            0
+        };
 
-        let element_right_weekend_format = element_mid_weekend_format.clone()
-            .set_border_right(FormatBorder::Thin);
+        self.shift_rows.iter().sum::<usize>() + labels
+    }
 
-        let bottom_left_format = Format::new()
-            .set_background_color(white)
-            .set_border_left(FormatBorder::Thin)
-            .set_border_bottom(FormatBorder::Thin);
+    pub fn export(&self, ext: FileExt) {
+        match ext {
+            FileExt::Xlsx => self.export_xlsx(),
+            FileExt::Csv => {}
+        }
+    }
 
-        let bottom_mid_format = bottom_left_format.clone()
-            .set_border_left(FormatBorder::None);
+    fn export_xlsx(&self) {
+        let output_path = DialogBuilder::file()
+            .add_filter("Excel File", ["xlsx"])
+            .save_single_file()
+            .show()
+            .unwrap();
 
-        let bottom_right_format = bottom_mid_format.clone()
-            .set_border_right(FormatBorder::Thin);
+        if let Some(path) = output_path {
+            self.save_xlsx(&path).unwrap();
+        }
+    }
 
-        let bottom_left_weekend_format = Format::new()
-            .set_background_color(wknd_color)
-            .set_border_left(FormatBorder::Thin)
-            .set_border_bottom(FormatBorder::Thin);
+    /// Writes the schedule to an excel workbook, with its ledger, stats and
+    /// trace on their own sheets.
+    pub fn save_xlsx(&self, path: &Path) -> Result<(), XlsxError> {
+        let mut workbook = Workbook::new();
 
-        let bottom_mid_weekend_format = bottom_left_weekend_format.clone()
-            .set_border_left(FormatBorder::None);
+        let working_formats = DayFormats::new(Color::RGB(0xffffff));
+        let off_formats = DayFormats::new(Color::RGB(0xfce4d6));
+        let holiday_formats = DayFormats::new(Color::RGB(0xe2efda));
 
-        let bottom_right_weekend_format = bottom_mid_weekend_format.clone()
-            .set_border_right(FormatBorder::Thin);
+        // The empty places of the shifts with fewer elements than they need
+        let gap_formats = DayFormats::new(Color::RGB(0xffc7ce));
 
         let worksheet = workbook.add_worksheet();
 
-        let month: Month = Month::from_u32(self.month.month());
-        let year = self.month.year();
+        // Days that aren't scheduled are colored like the weekend
+        let is_off_day = |day: u32| {
+            !self.schedule.calendar.is_working_day(Day::from_u32(day))
+        };
+
+        let day_formats = |date: Option<NaiveDate>, j: usize| {
+            let holiday = date
+                .and_then(|date| self.schedule.calendar.holiday(date));
+
+            if holiday.is_some() {
+                (&holiday_formats, holiday)
+            } else if is_off_day(j as u32) {
+                (&off_formats, None)
+            } else {
+                (&working_formats, None)
+            }
+        };
 
         worksheet.write(
-            0, 1, format!("Schedule for {} {}", month.as_str(), year)
+            0,
+            1,
+            format!(
+                "Schedule for {} {} (seed {})",
+                self.schedule.calendar.month.as_str(),
+                self.schedule.calendar.year,
+                self.schedule.seed,
+            ),
         ).unwrap();
 
         for i in 0..7 {
@@ -134,239 +199,301 @@ impl Writer {
                 1,
                 index+2,
                 day.as_str(),
-                if i == 0 || i == 6 {
-                    &merge_weekend_format
+                if is_off_day(i) {
+                    &off_formats.header
                 } else {
-                    &merge_format
+                    &working_formats.header
                 },
             ).unwrap();
         }
 
-        let mut day_number = 0;
-        let mut start_printing_dates = false;
         let mut week_index = 0;
 
         // fill all the dates in
-        for (i, week) in self.month_schedule.iter().enumerate() {
+        for (i, week) in self.schedule.calendar.weeks().iter().enumerate() {
             if i == 0 {
                 week_index = 2;
             } else {
-                week_index += (self.elem_num + 2) as u32;
+                week_index += (self.rows_per_week() + 2) as u32;
             }
 
-            for (j, day) in week.iter().enumerate() {
+            for (j, date) in week.iter().enumerate() {
                 let day_index: u16 = (j*3 + 1) as u16;
+                let (formats, holiday) = day_formats(*date, j);
+                let scheduled = date.is_some()
+                    && holiday.is_none()
+                    && !is_off_day(j as u32);
 
-                for k in 0..self.elem_num {
-                    worksheet.write_with_format(
-                        week_index + (k as u32) + 1,
+                match date {
+                    Some(date) => worksheet.write_number_with_format(
+                        week_index,
                         day_index,
-                        "",
-                        if j == 0 || j == 6 {
-                            &element_left_weekend_format
-                        } else {
-                            &element_left_format
-                        },
-                    ).unwrap();
+                        date.day(),
+                        &formats.date_left,
+                    ),
 
-                    worksheet.write_with_format(
-                        week_index + (k as u32) + 1,
-                        day_index + 1,
+                    None => worksheet.write_with_format(
+                        week_index,
+                        day_index,
                         "",
-                        if j == 0 || j == 6 {
-                            &element_mid_weekend_format
-                        } else {
-                            &element_mid_format
-                        },
-                    ).unwrap();
+                        &formats.date_left,
+                    ),
+                }.unwrap();
+
+                worksheet.write_with_format(
+                    week_index,
+                    day_index + 1,
+                    "",
+                    &formats.date_mid,
+                ).unwrap();
+
+                worksheet.write_with_format(
+                    week_index,
+                    day_index + 2,
+                    "",
+                    &formats.date_right,
+                ).unwrap();
+
+                let mut row = week_index + 1;
+
+                for (k, shift) in self.schedule.shifts.iter().enumerate() {
+                    let names = date
+                        .map(|date| self.schedule.names_in(date, k))
+                        .unwrap_or_default();
+
+                    let headcount = if scheduled {
+                        self.schedule.calendar
+                            .shift_headcount(Day::from_u32(j as u32), shift)
+                    } else {
+                        0
+                    };
 
-                    worksheet.write_with_format(
-                        week_index + (k as u32) + 1,
-                        day_index + 2,
-                        "",
-                        if j == 0 || j == 6 {
-                            &element_right_weekend_format
+                    let label = if self.shifts_labeled() && scheduled {
+                        shift.to_string()
+                    } else {
+                        String::new()
+                    };
+
+                    // The text of each row, its formats and whether it's the
+                    // shift's label
+                    let cells = iter::once((label, formats, true))
+                        .filter(|_| self.shifts_labeled())
+                        .chain((0..self.shift_rows[k]).map(|i| {
+                            match names.get(i) {
+                                Some(name) => (name.to_string(), formats, false),
+                                None if i < headcount => {
+                                    ("Unfilled".to_owned(), &gap_formats, false)
+                                }
+
+                                None => (String::new(), formats, false),
+                            }
+                        }));
+
+                    for (text, cell_formats, is_label) in cells {
+                        // Holidays are labeled with their name instead
+                        let text = match holiday {
+                            Some(holiday) if row == week_index + 1 => {
+                                holiday.name.clone()
+                            }
+
+                            _ => text,
+                        };
+
+                        let format = if is_label {
+                            &cell_formats.shift_mid
                         } else {
-                            &element_right_format
-                        },
-                    ).unwrap();
-                }
-
-                if !day.is_empty() {
-                    start_printing_dates = true;
-                } 
+                            &cell_formats.element_mid
+                        };
 
-                if j == 0 || j == 6 {
-                    if start_printing_dates {
-                        day_number += 1;
-
-                        worksheet.write_number_with_format(
-                            week_index,
-                            day_index,
-                            day_number,
-                            &date_weekend_format,
-                        ).unwrap();
-                    } else {
                         worksheet.write_with_format(
-                            week_index,
-                            day_index,
-                            "",
-                            &date_weekend_format,
+                            row, day_index, "", &cell_formats.element_left,
                         ).unwrap();
-                    }
-
-                    worksheet.write_with_format(
-                        week_index,
-                        day_index + 1,
-                        "",
-                        &date_mid_weekend_format,
-                    ).unwrap();
 
-                    worksheet.write_with_format(
-                        week_index,
-                        day_index + 2,
-                        "",
-                        &date_right_weekend_format,
-                    ).unwrap();
-
-                    for (k, elem) in day.iter().enumerate() {
                         worksheet.write_with_format(
-                            week_index + (k as u32) + 1,
-                            day_index,
-                            "",
-                            &element_left_weekend_format,
+                            row, day_index + 1, text, format,
                         ).unwrap();
 
                         worksheet.write_with_format(
-                            week_index + (k as u32) + 1,
-                            day_index + 1,
-                            elem,
-                            &element_mid_weekend_format,
+                            row, day_index + 2, "", &cell_formats.element_right,
                         ).unwrap();
 
-                        worksheet.write_with_format(
-                            week_index + (k as u32) + 1,
-                            day_index + 2,
-                            "",
-                            &element_right_weekend_format,
-                        ).unwrap();
-                    }
-                } else {
-                    if start_printing_dates {
-                        day_number += 1;
-
-                        worksheet.write_number_with_format(
-                            week_index,
-                            day_index,
-                            day_number,
-                            &date_format,
-                        ).unwrap();
-                    } else {
-                        worksheet.write_with_format(
-                            week_index,
-                            day_index,
-                            "",
-                            &date_format,
-                        ).unwrap();
+                        row += 1;
                     }
+                }
 
-                    worksheet.write_with_format(
-                        week_index,
-                        day_index + 1,
-                        "",
-                        &date_mid_format,
-                    ).unwrap();
+                let bottom_row = row;
 
-                    worksheet.write_with_format(
-                        week_index,
-                        day_index + 2,
-                        "",
-                        &date_right_format,
-                    ).unwrap();
+                worksheet.write_with_format(
+                    bottom_row, day_index, "", &formats.bottom_left,
+                ).unwrap();
 
-                    for (k, elem) in day.iter().enumerate() {
-                        worksheet.write_with_format(
-                            week_index + (k as u32) + 1,
-                            day_index,
-                            "",
-                            &element_left_format,
-                        ).unwrap();
+                worksheet.write_with_format(
+                    bottom_row, day_index + 1, "", &formats.bottom_mid,
+                ).unwrap();
 
-                        worksheet.write_with_format(
-                            week_index + (k as u32) + 1,
-                            day_index + 1,
-                            elem,
-                            &element_mid_format,
-                        ).unwrap();
+                worksheet.write_with_format(
+                    bottom_row, day_index + 2, "", &formats.bottom_right,
+                ).unwrap();
+            }
+        }
 
-                        worksheet.write_with_format(
-                            week_index + (k as u32) + 1,
-                            day_index + 2,
-                            "",
-                            &element_right_format,
-                        ).unwrap();
-                    }
-                }
+        self.write_ledger_sheet(workbook.add_worksheet());
+        self.write_stats_sheet(workbook.add_worksheet());
+
+        if !self.schedule.trace.is_empty() {
+            self.write_trace_sheet(workbook.add_worksheet());
+        }
+
+        workbook.save(path)
+    }
+
+    /// Writes the ledger of the schedule to its own sheet, so that the export
+    /// can be loaded as the previous period of the next schedule.
+    fn write_ledger_sheet(&self, worksheet: &mut Worksheet) {
+        let header = Format::new()
+            .set_bold()
+            .set_border_bottom(FormatBorder::Thin);
+
+        worksheet.set_name(LEDGER_SHEET).unwrap();
+        worksheet.set_column_width(0, 24).unwrap();
+
+        for (i, title) in ["Name", "Debt", "Rotation"].iter().enumerate() {
+            worksheet.write_with_format(0, i as u16, *title, &header).unwrap();
+        }
+
+        for (i, entry) in self.schedule.ledger.entries.iter().enumerate() {
+            let row = i as u32 + 1;
+
+            worksheet.write(row, 0, entry.name.as_str()).unwrap();
+            worksheet.write_number(row, 1, entry.debt).unwrap();
+
+            if let Some(rotation) = entry.rotation {
+                worksheet.write_number(row, 2, rotation as u32).unwrap();
             }
+        }
+    }
 
-            for j in 0..7 {
-                let day_index: u16 = (j*3 + 1) as u16;
+    /// Writes the statistics of the schedule to their own sheet: the days of
+    /// each element, then the figures of the whole month.
+    fn write_stats_sheet(&self, worksheet: &mut Worksheet) {
+        let header = Format::new()
+            .set_bold()
+            .set_border_bottom(FormatBorder::Thin);
+        let decimal = Format::new().set_num_format("0.00");
+        let stats = ScheduleStats::new(&self.schedule);
 
-                if j == 0 || j == 6 {
-                    worksheet.write_with_format(
-                        week_index + self.elem_num as u32 + 1,
-                        day_index,
-                        "",
-                        &bottom_left_weekend_format,
-                    ).unwrap();
+        worksheet.set_name(STATS_SHEET).unwrap();
+        worksheet.set_column_width(0, 24).unwrap();
 
-                    worksheet.write_with_format(
-                        week_index + self.elem_num as u32 + 1,
-                        day_index + 1,
-                        "",
-                        &bottom_mid_weekend_format,
-                    ).unwrap();
+        let titles = ["Name", "Days"].into_iter()
+            .chain(Day::ALL.iter().map(|day| &day.as_str()[..3]))
+            .chain(["Weekend", "Longest streak", "Debt"]);
 
-                    worksheet.write_with_format(
-                        week_index + self.elem_num as u32 + 1,
-                        day_index + 2,
-                        "",
-                        &bottom_right_weekend_format,
-                    ).unwrap();
-                } else {
-                    worksheet.write_with_format(
-                        week_index + self.elem_num as u32 + 1,
-                        day_index,
-                        "",
-                        &bottom_left_format,
-                    ).unwrap();
+        for (i, title) in titles.enumerate() {
+            worksheet.write_with_format(0, i as u16, title, &header).unwrap();
+        }
 
-                    worksheet.write_with_format(
-                        week_index + self.elem_num as u32 + 1,
-                        day_index + 1,
-                        "",
-                        &bottom_mid_format,
-                    ).unwrap();
+        for (i, elem) in stats.elements.iter().enumerate() {
+            let row = i as u32 + 1;
 
-                    worksheet.write_with_format(
-                        week_index + self.elem_num as u32 + 1,
-                        day_index + 2,
-                        "",
-                        &bottom_right_format,
-                    ).unwrap();
-                }
+            worksheet.write(row, 0, elem.name.as_str()).unwrap();
+            worksheet.write_number(row, 1, elem.total as u32).unwrap();
+
+            for (day, days) in elem.by_day.iter().enumerate() {
+                worksheet.write_number(row, day as u16 + 2, *days as u32)
+                    .unwrap();
             }
+
+            worksheet.write_number(row, 9, elem.weekend as u32).unwrap();
+            worksheet.write_number(row, 10, elem.longest_streak as u32).unwrap();
+            worksheet.write_number(row, 11, elem.debt).unwrap();
+        }
+
+        let row = stats.elements.len() as u32 + 2;
+
+        worksheet.write(row, 0, "Average days").unwrap();
+        worksheet.write_number_with_format(row, 1, stats.mean, &decimal)
+            .unwrap();
+        worksheet.write(row + 1, 0, "Standard deviation").unwrap();
+        worksheet.write_number_with_format(row + 1, 1, stats.std_dev, &decimal)
+            .unwrap();
+        worksheet.write(row + 2, 0, "Shifts not filled").unwrap();
+        worksheet.write_number(row + 2, 1, stats.unmet_slots as u32).unwrap();
+        worksheet.write(row + 3, 0, "Empty places").unwrap();
+        worksheet.write_number(row + 3, 1, stats.missing_places as u32).unwrap();
+    }
+
+    /// Writes the decisions of the rotation to their own sheet, one per row.
+    fn write_trace_sheet(&self, worksheet: &mut Worksheet) {
+        let header = Format::new()
+            .set_bold()
+            .set_border_bottom(FormatBorder::Thin);
+
+        worksheet.set_name(TRACE_SHEET).unwrap();
+        worksheet.set_column_width(0, 12).unwrap();
+        worksheet.set_column_width(1, 16).unwrap();
+        worksheet.set_column_width(2, 24).unwrap();
+        worksheet.set_column_width(4, 32).unwrap();
+
+        for (i, title) in ["Date", "Shift", "Name", "Decision", "Reason", "Debt"]
+            .iter()
+            .enumerate()
+        {
+            worksheet.write_with_format(0, i as u16, *title, &header).unwrap();
         }
 
+        for (i, entry) in self.schedule.trace.entries.iter().enumerate() {
+            let row = i as u32 + 1;
+
+            worksheet.write(row, 0, entry.date.format("%Y-%m-%d").to_string())
+                .unwrap();
+            worksheet.write(row, 1, entry.shift.as_str()).unwrap();
+            worksheet.write(row, 2, entry.name.as_str()).unwrap();
+            worksheet.write(row, 3, entry.action.to_string()).unwrap();
+            worksheet.write(row, 4, entry.reason.to_string()).unwrap();
+            worksheet.write_number(row, 5, entry.debt).unwrap();
+        }
+    }
+
+    /// Exports the decisions of the rotation as a .json file.
+    pub fn export_trace(&self) {
         let output_path = DialogBuilder::file()
-            .add_filter("Excel File", &[ ".xlsx" ])
+            .add_filter("JSON File", ["json"])
             .save_single_file()
             .show()
             .unwrap();
 
         if let Some(path) = output_path {
-            workbook.save(path).unwrap();
+            std::fs::write(path, self.schedule.trace.to_json()).unwrap();
+        }
+    }
+
+    /// Exports the ledger of the schedule alone, as a .csv file to load along
+    /// with the next period's input.
+    pub fn export_ledger(&self) {
+        let output_path = DialogBuilder::file()
+            .add_filter("CSV File", ["csv"])
+            .save_single_file()
+            .show()
+            .unwrap();
+
+        let Some(path) = output_path else {
+            return;
+        };
+
+        let mut csv_writer = csv::Writer::from_path(path).unwrap();
+
+        csv_writer.write_record(["Name", "Debt", "Rotation"]).unwrap();
+
+        for entry in self.schedule.ledger.entries.iter() {
+            csv_writer.write_record([
+                entry.name.clone(),
+                entry.debt.to_string(),
+                entry.rotation.map(|r| r.to_string()).unwrap_or_default(),
+            ]).unwrap();
         }
+
+        csv_writer.flush().unwrap();
     }
 }
 
@@ -0,0 +1,84 @@
+use std::{ fs, path::PathBuf };
+
+use schedulez::{ reader::Reader, types::Day };
+
+/// Writes the contents to a file of the temp directory that no other test or
+/// run uses.
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-{}", std::process::id(), name));
+    fs::write(&path, contents).unwrap();
+
+    path
+}
+
+fn read(name: &str, contents: &str) -> Reader {
+    let path = temp_file(name, contents);
+
+    let mut reader = Reader::default();
+    let result = reader.read(&path);
+    fs::remove_file(&path).unwrap();
+    result.unwrap();
+
+    reader
+}
+
+#[test]
+fn columns_are_found_in_any_order() {
+    let reader = read(
+        "columns-order.csv",
+        "Avoid Days,Name,Prefer Days\nFri,Ana,Mon\n",
+    );
+
+    assert_eq!(reader.elements.len(), 1);
+    assert_eq!(reader.elements[0].text, "Ana");
+    assert_eq!(reader.elements[0].prefer_days, vec![Day::Monday]);
+    assert_eq!(reader.elements[0].avoid_days, vec![Day::Friday]);
+}
+
+#[test]
+fn header_synonyms_are_recognized() {
+    let reader = read(
+        "columns-synonyms.csv",
+        "Days Off,employee_name,Preferred-Days\nFri,Ana,Mon\n",
+    );
+
+    assert_eq!(reader.elements[0].text, "Ana");
+    assert_eq!(reader.elements[0].prefer_days, vec![Day::Monday]);
+    assert_eq!(reader.elements[0].avoid_days, vec![Day::Friday]);
+}
+
+#[test]
+fn unknown_columns_are_kept_as_metadata() {
+    let reader = read(
+        "columns-metadata.csv",
+        "Name,Email,Prefer Days,Team\nAna,ana@example.com,Mon,Support\nBen,,,Sales\n",
+    );
+
+    assert_eq!(reader.elements[0].metadata, vec![
+        ("Email".to_owned(), "ana@example.com".to_owned()),
+        ("Team".to_owned(), "Support".to_owned()),
+    ]);
+    assert_eq!(reader.elements[1].metadata, vec![
+        ("Team".to_owned(), "Sales".to_owned()),
+    ]);
+}
+
+#[test]
+fn rows_after_the_first_element_are_not_headers() {
+    let reader = read(
+        "columns-data-headers.csv",
+        "Team,Name,Prefer Days\nSupport,Ana,Mon\nRole,Member,Tue\nSales,Ben,Wed\n",
+    );
+
+    let names: Vec<&str> = reader.elements.iter()
+        .map(|elem| elem.text.as_str())
+        .collect();
+    assert_eq!(names, vec!["Ana", "Member", "Ben"]);
+
+    assert_eq!(reader.elements[1].metadata, vec![
+        ("Team".to_owned(), "Role".to_owned()),
+    ]);
+    assert!(reader.elements[1].roles.is_empty());
+    assert_eq!(reader.elements[2].prefer_days, vec![Day::Wednesday]);
+}
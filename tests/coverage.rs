@@ -0,0 +1,125 @@
+use std::fs;
+
+use schedulez::{
+    calendar::Calendar,
+    coverage::Coverage,
+    reader::{ Reader, ScheduledElement },
+    schedule::ScheduleWarning,
+    types::{ Day, Month },
+    worker::{ self, Rules },
+};
+
+#[test]
+fn every_day_has_its_roles() {
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-coverage-file.csv", std::process::id()));
+    fs::write(&path, "Role,Min,Days\nSenior,1,\nSpanish,2,Mon-Wed\n").unwrap();
+
+    let mut reader = Reader::default();
+    reader.read_coverage(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(reader.row_errors.is_empty());
+    assert_eq!(
+        reader.coverage[1].days,
+        vec![Day::Monday, Day::Tuesday, Day::Wednesday],
+    );
+
+    let elements: Vec<ScheduledElement> = (0..10)
+        .map(|i| ScheduledElement {
+            text: format!("Element {}", i),
+            roles: match i {
+                0 | 1 => vec!["senior".to_owned()],
+                2 => vec!["Spanish".to_owned(), "Senior".to_owned()],
+                3 => vec!["Spanish".to_owned()],
+                _ => vec![],
+            },
+            ..ScheduledElement::default()
+        })
+        .collect();
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(3);
+    calendar.coverage = reader.coverage.clone();
+
+    let rules = Rules { seed: Some(2), ..Rules::default() };
+    let schedule = worker::process(&elements, &calendar, &rules);
+
+    assert!(schedule.warnings.is_empty(), "{:?}", schedule.warnings);
+
+    for (date, assignments) in schedule.by_date() {
+        if assignments.is_empty() {
+            continue;
+        }
+
+        assert_eq!(assignments.len(), 3);
+        assert!(assignments.iter()
+            .any(|a| schedule.element(a).has_role("Senior")), "{}", date);
+    }
+}
+
+#[test]
+fn impossible_coverage_is_reported() {
+    let elements: Vec<ScheduledElement> = (0..4)
+        .map(|i| ScheduledElement {
+            text: format!("Element {}", i),
+            roles: if i == 0 { vec!["First-aid".to_owned()] } else { vec![] },
+            avoid_days: if i == 0 { vec![Day::Friday] } else { vec![] },
+            ..ScheduledElement::default()
+        })
+        .collect();
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(2);
+    calendar.coverage = vec![Coverage::new("First-aid", 1)];
+
+    let rules = Rules { seed: Some(2), ..Rules::default() };
+    let schedule = worker::process(&elements, &calendar, &rules);
+
+    // Element 0 can't work on the four Fridays of the month
+    assert_eq!(schedule.warnings.len(), 4);
+    assert!(schedule.warnings.iter().all(|w| matches!(
+        w,
+        ScheduleWarning::RoleUncovered { covered: 0, min: 1, .. }
+    )));
+    assert!(schedule.warnings[0].to_string().contains("Fri 2026-11-06"));
+}
+
+#[test]
+fn preferences_dont_take_the_place_of_a_role() {
+    let elements: Vec<ScheduledElement> = (0..6)
+        .map(|i| ScheduledElement {
+            text: format!("Element {}", i),
+            roles: if i == 0 { vec!["Senior".to_owned()] } else { vec![] },
+            prefer_days: if i == 0 {
+                vec![]
+            } else {
+                vec![
+                    Day::Monday, Day::Tuesday, Day::Wednesday, Day::Thursday,
+                    Day::Friday,
+                ]
+            },
+            ..ScheduledElement::default()
+        })
+        .collect();
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(2);
+    calendar.coverage = vec![Coverage::new("Senior", 1)];
+
+    let rules = Rules {
+        seed: Some(5),
+        preference_weight: 1.0,
+        ..Rules::default()
+    };
+    let schedule = worker::process(&elements, &calendar, &rules);
+
+    assert!(!schedule.warnings.iter()
+        .any(|w| matches!(w, ScheduleWarning::RoleUncovered { .. })));
+
+    for (date, assignments) in schedule.by_date() {
+        if !assignments.is_empty() {
+            assert!(assignments.iter().any(|a| a.element_id == 0), "{}", date);
+        }
+    }
+}
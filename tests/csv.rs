@@ -0,0 +1,72 @@
+use std::{ fs, path::{ Path, PathBuf } };
+
+use schedulez::{
+    reader::{ ReadError, Reader },
+    types::{ Day, FileExt },
+};
+
+/// Writes the contents to a file of the temp directory that no other test or
+/// run uses.
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-{}", std::process::id(), name));
+    fs::write(&path, contents).unwrap();
+
+    path
+}
+
+#[test]
+fn csv_roster_is_read() {
+    let path = temp_file(
+        "csv-roster.csv",
+        "Name,Prefer Days,Avoid Days\nAna,\"Mon, Wed\",Fri\nBen,,\"Tue,Thu\"\n",
+    );
+
+    let mut reader = Reader::default();
+    let result = reader.read(&path);
+    fs::remove_file(&path).unwrap();
+
+    result.unwrap();
+    assert!(reader.row_errors.is_empty());
+    assert_eq!(reader.elements.len(), 2);
+
+    // The quoted day lists stay in their own column
+    assert_eq!(reader.elements[0].text, "Ana");
+    assert_eq!(reader.elements[0].prefer_days, vec![Day::Monday, Day::Wednesday]);
+    assert_eq!(reader.elements[0].avoid_days, vec![Day::Friday]);
+    assert_eq!(reader.elements[0].row, 2);
+
+    assert_eq!(reader.elements[1].text, "Ben");
+    assert!(reader.elements[1].prefer_days.is_empty());
+    assert_eq!(reader.elements[1].avoid_days, vec![Day::Tuesday, Day::Thursday]);
+}
+
+#[test]
+fn csv_without_header_uses_the_default_columns() {
+    let path = temp_file("csv-headerless.csv", "Ana,Mon,Fri\nBen,Tue,\n");
+
+    let mut reader = Reader::default();
+    let result = reader.read(&path);
+    fs::remove_file(&path).unwrap();
+
+    result.unwrap();
+    assert_eq!(reader.elements.len(), 2);
+    assert_eq!(reader.elements[0].prefer_days, vec![Day::Monday]);
+    assert_eq!(reader.elements[0].avoid_days, vec![Day::Friday]);
+    assert_eq!(reader.elements[1].text, "Ben");
+}
+
+#[test]
+fn file_ext_is_found_from_the_path() {
+    assert_eq!(FileExt::from_path(Path::new("roster.xlsx")), Some(FileExt::Xlsx));
+    assert_eq!(FileExt::from_path(Path::new("roster.csv")), Some(FileExt::Csv));
+    assert_eq!(FileExt::from_path(Path::new("Roster.CSV")), Some(FileExt::Csv));
+    assert_eq!(FileExt::from_path(Path::new("roster.xls")), None);
+    assert_eq!(FileExt::from_path(Path::new("roster")), None);
+
+    let mut reader = Reader::default();
+    assert!(matches!(
+        reader.read(Path::new("roster.ods")),
+        Err(ReadError::UnsupportedFormat(_)),
+    ));
+}
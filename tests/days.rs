@@ -0,0 +1,95 @@
+use std::fs;
+
+use schedulez::{
+    reader::Reader,
+    types::{ Day, DayNames, DayParseError, Language },
+};
+
+#[test]
+fn days_and_ranges_are_parsed() {
+    let names = DayNames::default();
+
+    assert_eq!(names.parse("Monday"), Ok(vec![Day::Monday]));
+    assert_eq!(names.parse(" mon. "), Ok(vec![Day::Monday]));
+    assert_eq!(
+        names.parse("Mon-Thu"),
+        Ok(vec![Day::Monday, Day::Tuesday, Day::Wednesday, Day::Thursday]),
+    );
+
+    // Ranges wrap around the end of the week
+    assert_eq!(
+        names.parse("Fri-Mon"),
+        Ok(vec![Day::Friday, Day::Saturday, Day::Sunday, Day::Monday]),
+    );
+}
+
+#[test]
+fn unknown_days_are_errors() {
+    let names = DayNames::default();
+
+    assert_eq!(
+        names.parse("Thrusday"),
+        Err(DayParseError::Unknown("Thrusday".to_owned())),
+    );
+    assert_eq!(
+        names.parse("Mon-Thrusday"),
+        Err(DayParseError::BadRange("Mon-Thrusday".to_owned())),
+    );
+    assert_eq!(
+        names.parse("Thrusday").unwrap_err().to_string(),
+        "Unknown day \"Thrusday\"",
+    );
+}
+
+#[test]
+fn localized_names_are_parsed() {
+    let german = DayNames::from(Language::German);
+    assert_eq!(german.parse("Mittwoch"), Ok(vec![Day::Wednesday]));
+    assert_eq!(german.parse("Mo-Mi"), Ok(vec![Day::Monday, Day::Tuesday, Day::Wednesday]));
+    assert_eq!(german.parse("Wochenende"), Ok(vec![Day::Sunday, Day::Saturday]));
+    assert!(german.parse("Monday").is_err());
+
+    let spanish = DayNames::from(Language::Spanish);
+    assert_eq!(spanish.parse("miércoles"), Ok(vec![Day::Wednesday]));
+    assert_eq!(spanish.parse("Sábado"), Ok(vec![Day::Saturday]));
+    assert_eq!(spanish.parse("fin de semana"), Ok(vec![Day::Sunday, Day::Saturday]));
+}
+
+#[test]
+fn groups_follow_the_working_days() {
+    let mut names = DayNames::default();
+
+    assert_eq!(
+        names.parse("weekdays"),
+        Ok(vec![Day::Monday, Day::Tuesday, Day::Wednesday, Day::Thursday, Day::Friday]),
+    );
+    assert_eq!(names.parse("Weekends"), Ok(vec![Day::Sunday, Day::Saturday]));
+
+    // A Sunday to Thursday week
+    names.working_days = [true, true, true, true, true, false, false];
+
+    assert_eq!(
+        names.parse("weekdays"),
+        Ok(vec![Day::Sunday, Day::Monday, Day::Tuesday, Day::Wednesday, Day::Thursday]),
+    );
+    assert_eq!(names.parse("weekends"), Ok(vec![Day::Friday, Day::Saturday]));
+}
+
+#[test]
+fn groups_read_are_parsed_again_when_the_working_days_change() {
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-days-groups.csv", std::process::id()));
+    fs::write(&path, "Name,Prefer Days,Avoid Days\nAna,Mon,weekends\n").unwrap();
+
+    let mut reader = Reader::default();
+    let result = reader.read(&path);
+    fs::remove_file(&path).unwrap();
+    result.unwrap();
+
+    assert_eq!(reader.elements[0].avoid_days, vec![Day::Sunday, Day::Saturday]);
+
+    reader.set_working_days([true, true, true, true, true, false, false]);
+
+    assert_eq!(reader.elements[0].prefer_days, vec![Day::Monday]);
+    assert_eq!(reader.elements[0].avoid_days, vec![Day::Friday, Day::Saturday]);
+}
@@ -0,0 +1,77 @@
+use chrono::Datelike;
+use schedulez::{
+    calendar::Calendar,
+    reader::ScheduledElement,
+    types::{ Day, Month },
+    worker::{ self, Rules },
+};
+
+fn elements(names: &[&str]) -> Vec<ScheduledElement> {
+    names.iter()
+        .map(|name| ScheduledElement {
+            text: name.to_string(),
+            ..ScheduledElement::default()
+        })
+        .collect()
+}
+
+#[test]
+fn each_weekday_gets_its_own_headcount() {
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(1);
+    calendar.set_headcount(Day::Monday, 3);
+    calendar.set_headcount(Day::Friday, 2);
+
+    let rules = Rules { seed: Some(1), ..Rules::default() };
+    let schedule = worker::process(&elements(&["A", "B", "C", "D"]), &calendar, &rules);
+
+    for (date, assignments) in schedule.by_date() {
+        let expected = match Day::from_u32(date.weekday().num_days_from_sunday()) {
+            Day::Monday => 3,
+            Day::Friday => 2,
+            Day::Saturday | Day::Sunday => 0,
+            _ => 1,
+        };
+
+        assert_eq!(assignments.len(), expected, "{}", date);
+    }
+}
+
+#[test]
+fn small_roster_leaves_out_the_elements_avoiding_the_day() {
+    let mut elements = elements(&["A", "B"]);
+    elements[0].avoid_days = vec![Day::Monday];
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(3);
+
+    let schedule = worker::process(&elements, &calendar, &Rules::default());
+
+    for (date, assignments) in schedule.by_date() {
+        let names: Vec<&str> = assignments.iter()
+            .map(|a| schedule.element(a).text.as_str())
+            .collect();
+
+        match date.weekday().num_days_from_sunday() {
+            0 | 6 => assert!(names.is_empty()),
+            1 => assert_eq!(names, vec!["B"], "{}", date),
+            _ => assert_eq!(names.len(), 2, "{}", date),
+        }
+    }
+}
+
+#[test]
+fn window_moves_through_the_whole_roster() {
+    let names = ["A", "B", "C", "D", "E", "F"];
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(1);
+
+    let rules = Rules { seed: Some(5), ..Rules::default() };
+    let schedule = worker::process(&elements(&names), &calendar, &rules);
+
+    // 21 weekdays over 6 elements
+    for (id, name) in names.iter().enumerate() {
+        let days = schedule.by_element(id).count();
+        assert!((3..=4).contains(&days), "{} has {} days", name, days);
+    }
+}
@@ -0,0 +1,91 @@
+use std::fs;
+
+use chrono::NaiveDate;
+use rust_xlsxwriter::{ ExcelDateTime, Format, Workbook };
+use schedulez::{
+    calendar::Calendar,
+    reader::{ ReadError, Reader, ScheduledElement },
+    types::Month,
+    worker::{ self, Rules },
+};
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 11, day).unwrap()
+}
+
+#[test]
+fn holidays_sheet_is_read() {
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-holidays-sheet.xlsx", std::process::id()));
+    let mut workbook = Workbook::new();
+
+    let elements = workbook.add_worksheet().set_name("Elements").unwrap();
+    elements.write_column(0, 0, ["Name", "A", "B", "C"]).unwrap();
+
+    let holidays = workbook.add_worksheet().set_name("Holidays").unwrap();
+    holidays.write_row(0, 0, ["Name", "Date"]).unwrap();
+    holidays.write_row(1, 0, ["Thanksgiving", "2026-11-26"]).unwrap();
+
+    // An excel date, with an empty name
+    holidays.write_datetime_with_format(
+        2, 1,
+        ExcelDateTime::from_ymd(2026, 11, 27).unwrap(),
+        &Format::new().set_num_format("yyyy-mm-dd"),
+    ).unwrap();
+
+    holidays.write_row(3, 0, ["Someday", "2026-13-01"]).unwrap();
+    workbook.save(&path).unwrap();
+
+    let mut reader = Reader::default();
+    let result = reader.read(&path);
+    fs::remove_file(&path).unwrap();
+    result.unwrap();
+
+    assert_eq!(reader.elements.len(), 3);
+
+    let holidays: Vec<(NaiveDate, &str)> = reader.holidays.iter()
+        .map(|holiday| (holiday.date, holiday.name.as_str()))
+        .collect();
+    assert_eq!(holidays, vec![(date(26), "Thanksgiving"), (date(27), "Holiday")]);
+
+    assert_eq!(reader.row_errors.len(), 1);
+    assert!(matches!(
+        &reader.row_errors[0],
+        ReadError::BadDate { row: 4, column: 2, value } if value == "2026-13-01",
+    ));
+}
+
+#[test]
+fn holidays_file_is_read_and_skipped() {
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-holidays-file.csv", std::process::id()));
+    fs::write(&path, "2026-11-11,Veterans Day\n26.11.2026,Thanksgiving\n").unwrap();
+
+    let mut reader = Reader::default();
+    let result = reader.read_holidays(&path);
+    fs::remove_file(&path).unwrap();
+    result.unwrap();
+
+    assert_eq!(reader.holidays.len(), 2);
+    assert_eq!(reader.holidays[1].date, date(26));
+
+    let elements: Vec<ScheduledElement> = ["A", "B", "C"].iter()
+        .map(|name| ScheduledElement {
+            text: name.to_string(),
+            ..ScheduledElement::default()
+        })
+        .collect();
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(2);
+    calendar.holidays = reader.holidays.clone();
+
+    let schedule = worker::process(&elements, &calendar, &Rules::default());
+
+    assert_eq!(schedule.on_date(date(11)).count(), 0);
+    assert_eq!(schedule.on_date(date(26)).count(), 0);
+    assert_eq!(schedule.on_date(date(25)).count(), 2);
+
+    // The 21 weekdays of November 2026, less the two holidays
+    assert_eq!(schedule.assignments.len(), 19 * 2);
+}
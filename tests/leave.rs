@@ -0,0 +1,53 @@
+use std::fs;
+
+use chrono::NaiveDate;
+use schedulez::{
+    calendar::Calendar,
+    reader::{ self, Reader },
+    types::Month,
+    worker::{ self, Rules },
+};
+
+#[test]
+fn nobody_is_scheduled_on_leave() {
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-leave-sheet.csv", std::process::id()));
+    fs::write(
+        &path,
+        "Name,Leave\n\
+        A,\"2026-11-03..2026-11-07, 2026-11-20\"\n\
+        B,2026-11-09..2026-11-13\n\
+        C,\n\
+        D,\n",
+    ).unwrap();
+
+    let mut reader = Reader::default();
+    reader.read(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(reader.row_errors.is_empty());
+    assert_eq!(reader.elements[0].leave.len(), 2);
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(3);
+
+    let rules = Rules { seed: Some(11), ..Rules::default() };
+    let schedule = worker::process(&reader.elements, &calendar, &rules);
+
+    for assignment in schedule.assignments.iter() {
+        let elem = schedule.element(assignment);
+
+        assert!(
+            !elem.leave.iter().any(|dates| dates.contains(assignment.date)),
+            "{} is on leave on {}",
+            elem.text,
+            assignment.date,
+        );
+    }
+
+    let date = |day| NaiveDate::from_ymd_opt(2026, 11, day).unwrap();
+    assert_eq!(schedule.names_on(date(10)).len(), 3);
+    assert!(!schedule.names_on(date(10)).contains(&"B"));
+
+    assert!(reader::parse_date_range("2026-11-07..2026-11-03").is_none());
+}
@@ -0,0 +1,70 @@
+use std::fs;
+
+use rust_xlsxwriter::Workbook;
+use schedulez::{
+    calendar::Calendar,
+    reader::{ ReadError, Reader, ScheduledElement },
+    types::Month,
+    worker::{ self, Rules },
+};
+
+#[test]
+fn ledger_is_read_and_carried_over() {
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-ledger-carry-over.csv", std::process::id()));
+    fs::write(&path, "Name,Debt,Rotation\nA,0,3\nB,5,0\nC,-1,1\nD,0,2\n")
+        .unwrap();
+
+    let mut reader = Reader::default();
+    reader.read_ledger(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(reader.row_errors.is_empty());
+    assert_eq!(reader.ledger.entries.len(), 4);
+    assert_eq!(reader.ledger.debt("B"), 5);
+    assert_eq!(reader.ledger.rotation("A"), Some(3));
+
+    let elements: Vec<ScheduledElement> = ["A", "B", "C", "D"].iter()
+        .map(|name| ScheduledElement {
+            text: name.to_string(),
+            ..ScheduledElement::default()
+        })
+        .collect();
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(1);
+
+    let rules = Rules {
+        seed: Some(7),
+        carry_over: reader.ledger.clone(),
+        preference_weight: 0.0,
+        ..Rules::default()
+    };
+    let schedule = worker::process(&elements, &calendar, &rules);
+
+    // The rotation continues at "B", whatever the seed
+    let first = &schedule.assignments[0];
+    assert_eq!(schedule.element(first).text, "B");
+    assert_eq!(schedule.ledger.entries.len(), 4);
+}
+
+#[test]
+fn export_without_ledger_sheet_is_an_error() {
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-ledger-missing.xlsx", std::process::id()));
+
+    // The calendar of an export made before there were ledgers
+    let mut workbook = Workbook::new();
+    let calendar = workbook.add_worksheet();
+    calendar.write(0, 1, "Schedule for October 2026").unwrap();
+    calendar.write_row(3, 1, ["A", "", "", "B"]).unwrap();
+    workbook.save(&path).unwrap();
+
+    let mut reader = Reader::default();
+    let result = reader.read_ledger(&path);
+    fs::remove_file(&path).unwrap();
+
+    assert!(matches!(result, Err(ReadError::MissingSheet("Ledger"))));
+    assert_eq!(result.unwrap_err().to_string(), "The \"Ledger\" sheet is missing");
+    assert!(reader.ledger.is_empty());
+}
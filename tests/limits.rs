@@ -0,0 +1,71 @@
+use chrono::Datelike;
+use schedulez::{
+    calendar::Calendar,
+    reader::ScheduledElement,
+    schedule::ScheduleWarning,
+    types::{ Day, Month },
+    worker::{ self, Rules },
+};
+
+fn element(name: &str, min: Option<usize>, max: Option<usize>) -> ScheduledElement {
+    ScheduledElement {
+        text: name.to_owned(),
+        min_assignments: min,
+        max_assignments: max,
+        ..ScheduledElement::default()
+    }
+}
+
+#[test]
+fn limits_are_met() {
+    let elements = vec![
+        element("Part-time", None, Some(8)),
+        element("Full-time", Some(18), None),
+        element("C", None, None),
+        element("D", None, None),
+        element("E", None, None),
+        element("F", None, None),
+    ];
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(3);
+
+    let rules = Rules { seed: Some(5), ..Rules::default() };
+    let schedule = worker::process(&elements, &calendar, &rules);
+
+    assert!(schedule.by_element(0).count() <= 8);
+    assert!(schedule.by_element(1).count() >= 18);
+    assert!(schedule.warnings.is_empty());
+
+    // Every working day is still fully staffed
+    for (date, assignments) in schedule.by_date() {
+        let day = Day::from_u32(date.weekday().num_days_from_sunday());
+
+        if calendar.is_working_day(day) {
+            assert_eq!(assignments.len(), 3, "{}", date);
+        }
+    }
+}
+
+#[test]
+fn unmet_minimum_is_reported() {
+    let mut elements = vec![
+        element("A", Some(25), None),
+        element("B", None, None),
+    ];
+    elements[0].avoid_days = vec![Day::Monday];
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(1);
+
+    let rules = Rules { seed: Some(5), ..Rules::default() };
+    let schedule = worker::process(&elements, &calendar, &rules);
+
+    assert_eq!(schedule.by_element(0).count(), 16);
+    assert_eq!(schedule.warnings, vec![ScheduleWarning::BelowMinimum {
+        element_id: 0,
+        name: "A".to_owned(),
+        assigned: 16,
+        min: 25,
+    }]);
+}
@@ -0,0 +1,128 @@
+use std::fs;
+
+use calamine::{ open_workbook, Data, Reader, Xlsx };
+use chrono::{ Datelike, NaiveDate };
+
+use schedulez::{
+    calendar::Calendar,
+    reader::ScheduledElement,
+    types::{ Day, Month },
+    worker::{ self, Rules },
+    writer::Writer,
+};
+
+const YEARS: std::ops::RangeInclusive<i32> = 2020..=2030;
+
+fn elements(count: usize) -> Vec<ScheduledElement> {
+    (0..count)
+        .map(|i| ScheduledElement {
+            text: format!("Element {}", i),
+            row: i + 2,
+            ..ScheduledElement::default()
+        })
+        .collect()
+}
+
+#[test]
+fn weeks_cover_every_day_of_every_month() {
+    for year in YEARS {
+        for month in Month::ALL {
+            let calendar = Calendar::new(year, month);
+            let weeks = calendar.weeks();
+
+            assert!(
+                (4..=6).contains(&weeks.len()),
+                "{} {} spans {} weeks", month, year, weeks.len(),
+            );
+
+            for week in weeks.iter() {
+                for (j, date) in week.iter().enumerate() {
+                    if let Some(date) = date {
+                        assert_eq!(date.weekday().num_days_from_sunday(), j as u32);
+                    }
+                }
+            }
+
+            let laid_out: Vec<NaiveDate> = weeks.iter()
+                .flat_map(|week| week.iter().flatten().copied())
+                .collect();
+
+            assert_eq!(laid_out, calendar.dates().collect::<Vec<NaiveDate>>());
+            assert_eq!(laid_out.len() as u32, calendar.num_days());
+        }
+    }
+}
+
+#[test]
+fn every_working_day_of_every_month_is_scheduled() {
+    let elements = elements(12);
+
+    for year in YEARS {
+        for month in Month::ALL {
+            let mut calendar = Calendar::new(year, month);
+            calendar.set_all_headcounts(3);
+
+            let schedule = worker::process(&elements, &calendar, &Rules::default());
+
+            for (date, assignments) in schedule.by_date() {
+                let day = Day::from_u32(date.weekday().num_days_from_sunday());
+                let expected = if calendar.is_working_day(day) { 3 } else { 0 };
+
+                assert_eq!(
+                    assignments.len(), expected,
+                    "{} has {} assignments", date, assignments.len(),
+                );
+            }
+
+            let by_week: usize = schedule.by_week().map(|week| week.len()).sum();
+            assert_eq!(by_week, schedule.assignments.len());
+        }
+    }
+}
+
+#[test]
+fn six_week_month_is_scheduled_to_its_last_day() {
+    // March 2025 starts on a Saturday and has 31 days
+    let mut calendar = Calendar::new(2025, Month::March);
+    calendar.set_all_headcounts(2);
+
+    assert_eq!(calendar.weeks().len(), 6);
+
+    let schedule = worker::process(&elements(5), &calendar, &Rules::default());
+    let last_day = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+
+    assert_eq!(schedule.on_date(last_day).count(), 2);
+}
+
+#[test]
+fn chosen_month_is_scheduled_and_written() {
+    let mut calendar = Calendar::new(2027, Month::February);
+    calendar.set_all_headcounts(2);
+
+    let rules = Rules { seed: Some(3), ..Rules::default() };
+    let schedule = worker::process(&elements(5), &calendar, &rules);
+
+    assert_eq!(schedule.assignments.len(), 20 * 2);
+    assert!(schedule.assignments.iter()
+        .all(|a| a.date.year() == 2027 && a.date.month() == 2));
+
+    let mut writer = Writer::default();
+    writer.init(&schedule);
+
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-months-export.xlsx", std::process::id()));
+    writer.save_xlsx(&path).unwrap();
+
+    let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+    let range = workbook.worksheet_range_at(0).unwrap().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        range.get_value((0, 1)),
+        Some(&Data::String("Schedule for February 2027 (seed 3)".to_owned())),
+    );
+
+    // February 2027 starts on a Monday, in the second column of days
+    assert_eq!(range.get_value((2, 4)), Some(&Data::Float(1.0)));
+    assert_eq!(range.get_value((2, 1)), Some(&Data::Empty));
+}
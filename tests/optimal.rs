@@ -0,0 +1,150 @@
+use std::collections::HashSet;
+
+use chrono::{ Datelike, NaiveDate };
+use schedulez::{
+    calendar::{ Calendar, DateRange },
+    pairing::{ Pairing, PairRule },
+    reader::ScheduledElement,
+    schedule::Schedule,
+    scheduler::Scheduler,
+    solver::{ self, Optimal, Weights },
+    types::{ Day, Month },
+    worker::{ Rotation, Rules },
+};
+
+fn element(name: &str) -> ScheduledElement {
+    ScheduledElement { text: name.to_owned(), ..ScheduledElement::default() }
+}
+
+fn dates_of(schedule: &Schedule, id: usize) -> HashSet<NaiveDate> {
+    schedule.by_element(id).map(|a| a.date).collect()
+}
+
+#[test]
+fn optimal_keeps_hard_rules_and_scores_no_worse() {
+    let mut elements: Vec<ScheduledElement> = ["A", "B", "C", "D", "E", "F", "G"]
+        .iter()
+        .map(|name| element(name))
+        .collect();
+    elements[0].avoid_days = vec![Day::Monday];
+    elements[1].prefer_days = vec![Day::Friday];
+    elements[2].max_assignments = Some(6);
+    elements[3].leave = vec![DateRange::new(
+        NaiveDate::from_ymd_opt(2026, 11, 9).unwrap(),
+        NaiveDate::from_ymd_opt(2026, 11, 13).unwrap(),
+    )];
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(3);
+
+    let optimal = Optimal { iterations: 5_000, ..Optimal::default() };
+
+    for seed in 0..5 {
+        let rules = Rules {
+            seed: Some(seed),
+            max_consecutive_days: Some(4),
+            min_days_off: 1,
+            pairings: vec![Pairing::new("E", "F", PairRule::Apart)],
+            ..Rules::default()
+        };
+
+        let rotation = Rotation.schedule(&elements, &calendar, &rules);
+        let schedule = optimal.schedule(&elements, &calendar, &rules);
+        let weights = Weights::default();
+
+        assert!(
+            solver::evaluate(&schedule, &rules, &weights).total
+                <= solver::evaluate(&rotation, &rules, &weights).total,
+            "seed {}", seed,
+        );
+
+        for assignment in schedule.by_element(0) {
+            assert_ne!(assignment.date.weekday().num_days_from_sunday(), 1);
+        }
+
+        assert!(schedule.by_element(2).count() <= 6);
+
+        let leave = &elements[3].leave[0];
+        assert!(schedule.by_element(3).all(|a| !leave.contains(a.date)));
+        assert!(dates_of(&schedule, 4).is_disjoint(&dates_of(&schedule, 5)));
+
+        for id in 0..elements.len() {
+            let mut dates: Vec<NaiveDate> = dates_of(&schedule, id)
+                .into_iter()
+                .collect();
+            dates.sort();
+
+            let longest = dates.windows(5)
+                .any(|run| (run[4] - run[0]).num_days() == 4);
+            assert!(!longest, "seed {} element {}", seed, id);
+        }
+
+        for (date, assignments) in schedule.by_date() {
+            let dates: HashSet<usize> = assignments.iter()
+                .map(|a| a.element_id)
+                .collect();
+            assert_eq!(dates.len(), assignments.len(), "{}", date);
+        }
+    }
+}
+
+#[test]
+fn optimal_is_reproducible() {
+    let elements: Vec<ScheduledElement> = ["A", "B", "C", "D", "E"]
+        .iter()
+        .map(|name| element(name))
+        .collect();
+
+    let mut calendar = Calendar::new(2026, Month::December);
+    calendar.set_all_headcounts(2);
+
+    let rules = Rules { seed: Some(11), ..Rules::default() };
+    let optimal = Optimal { iterations: 2_000, ..Optimal::default() };
+
+    let first = optimal.schedule(&elements, &calendar, &rules);
+    let second = optimal.schedule(&elements, &calendar, &rules);
+
+    assert_eq!(first.assignments, second.assignments);
+    assert_eq!(first.seed, 11);
+}
+
+#[test]
+fn broken_start_is_repaired() {
+    let mut elements: Vec<ScheduledElement> = ["A", "B", "C", "D", "E"].iter()
+        .map(|name| element(name))
+        .collect();
+    elements[2].avoid_days = vec![Day::Monday];
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(2);
+
+    let rules = Rules {
+        seed: Some(1),
+        pairings: vec![
+            Pairing::new("A", "B", PairRule::Apart),
+            Pairing::new("D", "E", PairRule::Together),
+        ],
+        ..Rules::default()
+    };
+
+    // A and B share every Monday, C works on the Mondays it avoids and D
+    // works without E
+    let mut start = Schedule::new(&calendar, &elements, 1);
+
+    for date in calendar.dates() {
+        if date.weekday().num_days_from_sunday() == 1 {
+            start.assign(date, 0, 0);
+            start.assign(date, 1, 0);
+            start.assign(date, 2, 0);
+            start.assign(date, 3, 0);
+        }
+    }
+
+    let schedule = Optimal { iterations: 2_000, ..Optimal::default() }
+        .improve(start, &rules);
+
+    assert!(dates_of(&schedule, 0).is_disjoint(&dates_of(&schedule, 1)));
+    assert!(schedule.by_element(2)
+        .all(|a| a.date.weekday().num_days_from_sunday() != 1));
+    assert_eq!(dates_of(&schedule, 3), dates_of(&schedule, 4));
+}
@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+use rust_xlsxwriter::Workbook;
+use schedulez::{
+    calendar::Calendar,
+    pairing::{ Pairing, PairRule },
+    reader::{ Reader, ScheduledElement },
+    schedule::Schedule,
+    scheduler::Scheduler,
+    solver::Optimal,
+    types::{ Day, Month },
+    worker::{ self, Rules },
+};
+
+fn dates_of(schedule: &Schedule, name: &str) -> HashSet<NaiveDate> {
+    let id = schedule.elements.iter()
+        .position(|elem| elem.text == name)
+        .unwrap();
+
+    schedule.by_element(id).map(|a| a.date).collect()
+}
+
+#[test]
+fn pairs_are_read_and_honored() {
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-pairs-sheet.xlsx", std::process::id()));
+    let mut workbook = Workbook::new();
+
+    let elements = workbook.add_worksheet().set_name("Elements").unwrap();
+    elements.write(0, 0, "Name").unwrap();
+
+    for (i, name) in ["Mentor", "Mentee", "Key A", "Key B", "E", "F", "G", "H"]
+        .iter()
+        .enumerate()
+    {
+        elements.write(i as u32 + 1, 0, *name).unwrap();
+    }
+
+    let pairs = workbook.add_worksheet().set_name("Pairs").unwrap();
+    pairs.write_row(0, 0, ["Name", "With", "Rule"]).unwrap();
+    pairs.write_row(1, 0, ["Mentor", "Mentee", "Together"]).unwrap();
+    pairs.write_row(2, 0, ["Key A", "Key B", "never"]).unwrap();
+
+    workbook.save(&path).unwrap();
+
+    let mut reader = Reader::default();
+    reader.read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(reader.row_errors.is_empty());
+    assert_eq!(reader.elements.len(), 8);
+    assert_eq!(reader.pairings.len(), 2);
+    assert_eq!(reader.pairings[1].rule, PairRule::Apart);
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(3);
+
+    for seed in 0..20 {
+        let rules = Rules {
+            seed: Some(seed),
+            pairings: reader.pairings.clone(),
+            ..Rules::default()
+        };
+        let schedule = worker::process(&reader.elements, &calendar, &rules);
+
+        let mentor = dates_of(&schedule, "Mentor");
+        assert!(!mentor.is_empty());
+        assert_eq!(mentor, dates_of(&schedule, "Mentee"), "seed {}", seed);
+
+        let key_a = dates_of(&schedule, "Key A");
+        assert!(key_a.is_disjoint(&dates_of(&schedule, "Key B")), "seed {}", seed);
+
+        for (date, assignments) in schedule.by_date() {
+            assert!(assignments.is_empty() || assignments.len() == 3, "{}", date);
+        }
+    }
+}
+
+#[test]
+fn preferences_dont_bring_pairs_together() {
+    let elements: Vec<ScheduledElement> = ["A", "B", "C", "D", "E", "F", "G", "H"]
+        .iter()
+        .map(|name| ScheduledElement {
+            text: name.to_string(),
+            prefer_days: if ["A", "B"].contains(name) {
+                vec![Day::Monday]
+            } else {
+                vec![]
+            },
+            ..ScheduledElement::default()
+        })
+        .collect();
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(3);
+
+    for preference_weight in [0.5, 1.0] {
+        for seed in 0..20 {
+            let rules = Rules {
+                seed: Some(seed),
+                preference_weight,
+                pairings: vec![Pairing::new("A", "B", PairRule::Apart)],
+                ..Rules::default()
+            };
+
+            for schedule in [
+                worker::process(&elements, &calendar, &rules),
+                Optimal::default().schedule(&elements, &calendar, &rules),
+            ] {
+                assert!(
+                    dates_of(&schedule, "A").is_disjoint(&dates_of(&schedule, "B")),
+                    "weight {} seed {}", preference_weight, seed,
+                );
+            }
+        }
+    }
+}
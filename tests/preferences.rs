@@ -0,0 +1,69 @@
+use chrono::Datelike;
+use schedulez::{
+    calendar::Calendar,
+    reader::ScheduledElement,
+    schedule::Schedule,
+    types::{ Day, Month },
+    worker::{ self, Rules },
+};
+
+fn elements(prefer_days: Vec<Day>) -> Vec<ScheduledElement> {
+    let mut elements: Vec<ScheduledElement> = ["A", "B", "C", "D", "E"].iter()
+        .map(|name| ScheduledElement {
+            text: name.to_string(),
+            ..ScheduledElement::default()
+        })
+        .collect();
+    elements[0].prefer_days = prefer_days;
+
+    elements
+}
+
+fn schedule(elements: &[ScheduledElement], weight: f32, seed: u64) -> Schedule {
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(1);
+
+    let rules = Rules {
+        seed: Some(seed),
+        preference_weight: weight,
+        ..Rules::default()
+    };
+
+    worker::process(elements, &calendar, &rules)
+}
+
+/// The number of Mondays the first element is scheduled on.
+fn mondays(schedule: &Schedule) -> usize {
+    schedule.by_element(0)
+        .filter(|a| a.date.weekday().num_days_from_sunday() == 1)
+        .count()
+}
+
+#[test]
+fn preferred_days_are_favored() {
+    let elements = elements(vec![Day::Monday]);
+
+    let ignored: usize = (0..10)
+        .map(|seed| mondays(&schedule(&elements, 0.0, seed)))
+        .sum();
+    let weighted: usize = (0..10)
+        .map(|seed| mondays(&schedule(&elements, 1.0, seed)))
+        .sum();
+
+    assert!(weighted >= 2 * ignored, "{} Mondays, {} without the preference", weighted, ignored);
+}
+
+#[test]
+fn zero_weight_ignores_preferences() {
+    let preferring = elements(vec![Day::Monday, Day::Tuesday]);
+    let plain = elements(Vec::new());
+
+    for seed in 0..10 {
+        assert_eq!(
+            schedule(&preferring, 0.0, seed).assignments,
+            schedule(&plain, 0.0, seed).assignments,
+            "seed {}",
+            seed,
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use std::{ fs, path::{ Path, PathBuf } };
+
+use schedulez::reader::{ ReadError, Reader };
+
+/// Writes the contents to a file of the temp directory that no other test or
+/// run uses.
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-{}", std::process::id(), name));
+    fs::write(&path, contents).unwrap();
+
+    path
+}
+
+fn read(name: &str, contents: &str) -> (Reader, Result<(), ReadError>) {
+    let path = temp_file(name, contents);
+
+    let mut reader = Reader::default();
+    let result = reader.read(&path);
+    fs::remove_file(&path).unwrap();
+
+    (reader, result)
+}
+
+#[test]
+fn missing_file_is_an_io_error() {
+    let path = std::env::temp_dir().join(format!(
+        "schedulez-{}-missing.csv", std::process::id()
+    ));
+
+    let result = Reader::default().read(&path);
+    assert!(matches!(result, Err(ReadError::Io(_))), "{:?}", result);
+}
+
+#[test]
+fn other_formats_are_unsupported() {
+    let result = Reader::default().read(Path::new("roster.txt"));
+    assert!(matches!(result, Err(ReadError::UnsupportedFormat(_))));
+
+    // Not a workbook, whatever its extension
+    let (_, result) = read("read-errors-bad.xlsx", "Name,Prefer Days\n");
+    assert!(matches!(result, Err(ReadError::UnsupportedFormat(_))), "{:?}", result);
+}
+
+#[test]
+fn header_without_name_is_a_missing_column() {
+    let (_, result) = read(
+        "read-errors-no-name.csv",
+        "Prefer Days,Avoid Days\nMon,Fri\n",
+    );
+
+    assert!(matches!(result, Err(ReadError::MissingColumn("Name"))));
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "The \"Name\" column is missing",
+    );
+}
+
+#[test]
+fn values_like_headers_are_not_a_missing_column() {
+    let (reader, result) = read(
+        "read-errors-headerless.csv",
+        "Ana,Mon,Preferred\nBen,Tue,\n",
+    );
+
+    result.unwrap();
+    assert_eq!(reader.elements.len(), 2);
+    assert_eq!(reader.elements[0].text, "Ana");
+}
+
+#[test]
+fn bad_days_are_collected_with_their_cell() {
+    let (reader, result) = read(
+        "read-errors-days.csv",
+        "Name,Prefer Days,Avoid Days\nAna,\"Mon, Thrusday\",Fri\nBen,,Someday\n",
+    );
+
+    // Bad days don't stop the file from being read
+    result.unwrap();
+    assert_eq!(reader.elements.len(), 2);
+    assert_eq!(reader.elements[0].prefer_days.len(), 1);
+
+    let errors: Vec<String> = reader.row_errors.iter()
+        .map(|e| e.to_string())
+        .collect();
+    assert_eq!(errors, vec![
+        "Unknown day \"Thrusday\" at row 2, column B",
+        "Unknown day \"Someday\" at row 3, column C",
+    ]);
+    assert!(matches!(
+        &reader.row_errors[0],
+        ReadError::BadDayToken { row: 2, column: 2, token } if token == "Thrusday",
+    ));
+}
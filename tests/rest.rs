@@ -0,0 +1,92 @@
+use chrono::{ Datelike, NaiveDate, NaiveTime };
+use schedulez::{
+    calendar::Calendar,
+    reader::ScheduledElement,
+    shift::Shift,
+    types::{ Day, Month },
+    worker::{ self, Rules },
+};
+
+fn elements(count: usize) -> Vec<ScheduledElement> {
+    (0..count)
+        .map(|i| ScheduledElement {
+            text: format!("Element {}", i),
+            ..ScheduledElement::default()
+        })
+        .collect()
+}
+
+#[test]
+fn streaks_are_limited_and_followed_by_days_off() {
+    let elements = elements(6);
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(3);
+
+    for day in Day::ALL {
+        calendar.set_working_day(day, true);
+    }
+
+    let rules = Rules {
+        seed: Some(9),
+        max_consecutive_days: Some(3),
+        min_days_off: 2,
+        ..Rules::default()
+    };
+    let schedule = worker::process(&elements, &calendar, &rules);
+
+    for id in 0..elements.len() {
+        let dates: Vec<NaiveDate> = schedule.by_element(id)
+            .map(|a| a.date)
+            .collect();
+
+        let mut streak = 1;
+
+        for pair in dates.windows(2) {
+            let days_off = (pair[1] - pair[0]).num_days() - 1;
+
+            if days_off == 0 {
+                streak += 1;
+                assert!(streak <= 3, "Element {} on {}", id, pair[1]);
+            } else {
+                assert!(streak < 3 || days_off >= 2, "Element {} on {}", id, pair[1]);
+                streak = 1;
+            }
+        }
+    }
+}
+
+#[test]
+fn night_shift_is_not_followed_by_morning() {
+    let elements = elements(4);
+    let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_shifts(vec![
+        Shift::new("Morning", time(6), time(14), Some(1)),
+        Shift::new("Night", time(22), time(6), Some(1)),
+    ]);
+
+    let rules = Rules {
+        seed: Some(1),
+        min_rest_hours: Some(11),
+        ..Rules::default()
+    };
+    let schedule = worker::process(&elements, &calendar, &rules);
+
+    for night in schedule.assignments.iter().filter(|a| a.shift == 1) {
+        let next_day = night.date.succ_opt().unwrap();
+
+        assert!(!schedule.assignments.iter().any(|a| a.date == next_day
+            && a.shift == 0
+            && a.element_id == night.element_id));
+    }
+
+    for (date, assignments) in schedule.by_date() {
+        let day = Day::from_u32(date.weekday().num_days_from_sunday());
+
+        if calendar.is_working_day(day) {
+            assert_eq!(assignments.len(), 2, "{}", date);
+        }
+    }
+}
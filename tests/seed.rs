@@ -0,0 +1,77 @@
+use std::fs;
+
+use calamine::{ open_workbook, Data, Reader, Xlsx };
+use schedulez::{
+    calendar::Calendar,
+    reader::ScheduledElement,
+    types::Month,
+    worker::{ self, Rules },
+    writer::Writer,
+};
+
+fn elements() -> Vec<ScheduledElement> {
+    (0..8)
+        .map(|i| ScheduledElement {
+            text: format!("Element {}", i),
+            ..ScheduledElement::default()
+        })
+        .collect()
+}
+
+fn calendar() -> Calendar {
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(3);
+
+    calendar
+}
+
+#[test]
+fn same_seed_gives_same_schedule() {
+    let rules = Rules { seed: Some(42), ..Rules::default() };
+    let first = worker::process(&elements(), &calendar(), &rules);
+    let second = worker::process(&elements(), &calendar(), &rules);
+
+    assert_eq!(first.seed, 42);
+    assert_eq!(first.assignments, second.assignments);
+}
+
+#[test]
+fn different_seeds_give_different_schedules() {
+    let schedules: Vec<_> = (0..5)
+        .map(|seed| {
+            let rules = Rules { seed: Some(seed), ..Rules::default() };
+            worker::process(&elements(), &calendar(), &rules).assignments
+        })
+        .collect();
+
+    for (i, first) in schedules.iter().enumerate() {
+        for second in schedules[i + 1..].iter() {
+            assert_ne!(first, second);
+        }
+    }
+}
+
+#[test]
+fn random_seed_is_recorded() {
+    let first = worker::process(&elements(), &calendar(), &Rules::default());
+    let rules = Rules { seed: Some(first.seed), ..Rules::default() };
+    let second = worker::process(&elements(), &calendar(), &rules);
+
+    assert_eq!(first.assignments, second.assignments);
+
+    let mut writer = Writer::default();
+    writer.init(&first);
+
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-seed-export.xlsx", std::process::id()));
+    writer.save_xlsx(&path).unwrap();
+
+    let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+    let range = workbook.worksheet_range_at(0).unwrap().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        range.get_value((0, 1)),
+        Some(&Data::String(format!("Schedule for November 2026 (seed {})", first.seed))),
+    );
+}
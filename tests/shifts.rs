@@ -0,0 +1,67 @@
+use std::fs;
+
+use chrono::NaiveTime;
+use schedulez::{
+    calendar::Calendar,
+    reader::{ self, Reader, ScheduledElement },
+    types::{ Day, Month },
+    worker::{ self, Rules },
+};
+
+#[test]
+fn every_shift_is_filled_once_per_element() {
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-shifts-file.csv", std::process::id()));
+    fs::write(
+        &path,
+        "Shift,Start,End,Headcount\n\
+        Morning,06:00,14:00,2\n\
+        Evening,2:00 PM,22:00,2\n\
+        Night,22:00,06:00,1\n",
+    ).unwrap();
+
+    let mut reader = Reader::default();
+    reader.read_shifts(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(reader.row_errors.is_empty());
+    assert_eq!(reader.shifts.len(), 3);
+    assert_eq!(reader.shifts[1].start, NaiveTime::from_hms_opt(14, 0, 0).unwrap());
+    assert!(reader.shifts[2].ends_next_day());
+
+    let elements: Vec<ScheduledElement> = (0..9)
+        .map(|i| ScheduledElement {
+            text: format!("Element {}", i),
+            avoid_shifts: if i == 0 { vec!["night".to_owned()] } else { vec![] },
+            ..ScheduledElement::default()
+        })
+        .collect();
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_shifts(reader.shifts.clone());
+    assert_eq!(calendar.total_headcount(Day::Monday), 5);
+
+    let rules = Rules { seed: Some(3), ..Rules::default() };
+    let schedule = worker::process(&elements, &calendar, &rules);
+
+    for (date, assignments) in schedule.by_date() {
+        if assignments.is_empty() {
+            continue;
+        }
+
+        for (k, shift) in calendar.shifts.iter().enumerate() {
+            let names = schedule.names_in(date, k);
+            assert_eq!(names.len(), shift.headcount.unwrap(), "{} {}", date, shift);
+            assert!(k != 2 || !names.contains(&"Element 0"));
+        }
+
+        let mut ids: Vec<usize> = assignments.iter()
+            .map(|a| a.element_id)
+            .collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), assignments.len(), "{}", date);
+    }
+
+    assert_eq!(reader::parse_time("6 AM"), NaiveTime::from_hms_opt(6, 0, 0));
+}
@@ -0,0 +1,106 @@
+use chrono::NaiveDate;
+use schedulez::{
+    calendar::Calendar,
+    ledger::{ Ledger, LedgerEntry },
+    reader::ScheduledElement,
+    schedule::Schedule,
+    stats::ScheduleStats,
+    types::{ Day, Month },
+    worker::{ self, Rules },
+};
+
+fn element(name: &str) -> ScheduledElement {
+    ScheduledElement { text: name.to_owned(), ..ScheduledElement::default() }
+}
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 11, day).unwrap()
+}
+
+#[test]
+fn stats_count_days_streaks_and_gaps() {
+    let elements = vec![element("A"), element("B")];
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(1);
+
+    // Sun 1 to Tue 3 in a row, then Sat 7
+    let mut schedule = Schedule::new(&calendar, &elements, 0);
+
+    for day in [1, 2, 3, 7] {
+        schedule.assign(date(day), 0, 0);
+    }
+
+    schedule.assign(date(4), 1, 0);
+    schedule.ledger = Ledger {
+        entries: vec![LedgerEntry { name: "B".to_owned(), debt: 2, rotation: None }],
+    };
+
+    let stats = ScheduleStats::new(&schedule);
+    let a = &stats.elements[0];
+
+    assert_eq!(a.total, 4);
+    assert_eq!(a.by_day[Day::Sunday.to_u32() as usize], 1);
+    assert_eq!(a.by_day[Day::Saturday.to_u32() as usize], 1);
+    assert_eq!(a.longest_streak, 3);
+    assert_eq!(a.debt, 0);
+
+    assert_eq!(stats.elements[1].total, 1);
+    assert_eq!(stats.elements[1].debt, 2);
+
+    assert!((stats.mean - 2.5).abs() < 1e-9);
+    assert!((stats.std_dev - 1.5).abs() < 1e-9);
+
+    // The other 18 of the 21 weekdays are empty
+    assert_eq!(stats.unmet_slots, 18);
+    assert_eq!(stats.missing_places, 18);
+}
+
+#[test]
+fn stats_follow_the_generated_schedule() {
+    let elements: Vec<ScheduledElement> = ["A", "B", "C", "D"].iter()
+        .map(|name| element(name))
+        .collect();
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(2);
+
+    let rules = Rules { seed: Some(3), ..Rules::default() };
+    let schedule = worker::process(&elements, &calendar, &rules);
+    let stats = ScheduleStats::new(&schedule);
+
+    assert_eq!(
+        stats.elements.iter().map(|e| e.total).sum::<usize>(),
+        schedule.assignments.len(),
+    );
+    assert_eq!(stats.unmet_slots, 0);
+
+    for elem in stats.elements.iter() {
+        assert_eq!(elem.by_day.iter().sum::<usize>(), elem.total);
+        assert_eq!(elem.debt, schedule.ledger.debt(&elem.name));
+    }
+}
+
+#[test]
+fn weekend_counts_working_saturdays_and_sundays() {
+    let elements: Vec<ScheduledElement> = ["A", "B", "C", "D"].iter()
+        .map(|name| element(name))
+        .collect();
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_working_day(Day::Saturday, true);
+    calendar.set_working_day(Day::Monday, false);
+    calendar.set_all_headcounts(2);
+
+    let rules = Rules { seed: Some(3), ..Rules::default() };
+    let schedule = worker::process(&elements, &calendar, &rules);
+    let stats = ScheduleStats::new(&schedule);
+
+    // The four Saturdays, two elements each
+    assert_eq!(stats.elements.iter().map(|e| e.weekend).sum::<usize>(), 8);
+
+    for elem in stats.elements.iter() {
+        assert_eq!(
+            elem.weekend,
+            elem.by_day[Day::Saturday.to_u32() as usize]
+                + elem.by_day[Day::Sunday.to_u32() as usize],
+        );
+    }
+}
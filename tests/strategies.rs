@@ -0,0 +1,165 @@
+use std::collections::HashSet;
+
+use chrono::{ Datelike, NaiveDate };
+use schedulez::{
+    calendar::Calendar,
+    pairing::{ Pairing, PairRule },
+    reader::ScheduledElement,
+    schedule::Schedule,
+    scheduler::{ Draft, Registry, Scheduler },
+    types::{ Day, Month },
+    worker::Rules,
+};
+
+fn element(name: &str) -> ScheduledElement {
+    ScheduledElement { text: name.to_owned(), ..ScheduledElement::default() }
+}
+
+fn dates_of(schedule: &Schedule, id: usize) -> HashSet<NaiveDate> {
+    schedule.by_element(id).map(|a| a.date).collect()
+}
+
+/// Schedules the elements in the order of their names, as a team's own
+/// scheduler would.
+struct Alphabetical;
+
+impl Scheduler for Alphabetical {
+    fn name(&self) -> &str { "Alphabetical" }
+
+    fn schedule(
+        &self,
+        elements: &[ScheduledElement],
+        calendar: &Calendar,
+        rules: &Rules,
+    ) -> Schedule {
+        let mut draft = Draft::new(elements, calendar, rules);
+        let mut order: Vec<usize> = (0..elements.len()).collect();
+        order.sort_by_key(|id| elements[*id].text.clone());
+
+        for (date, shift, headcount) in draft.slots() {
+            let mut filled = 0;
+
+            for id in order.iter() {
+                if filled < headcount && draft.can_assign(*id, date, shift) {
+                    filled += draft.assign(*id, date, shift);
+                }
+            }
+        }
+
+        draft.finish()
+    }
+}
+
+#[test]
+fn schedulers_are_registered_by_name() {
+    let mut registry = Registry::default();
+
+    assert_eq!(
+        registry.names(),
+        ["Fast rotation", "Optimal", "Round-robin", "Random draw", "Weighted"],
+    );
+
+    registry.register(Alphabetical);
+    assert_eq!(registry.names().last().unwrap(), "Alphabetical");
+    assert!(registry.get("Alphabetical").is_some());
+    assert!(registry.get("Unknown").is_none());
+
+    // The same name replaces the scheduler
+    registry.register(Alphabetical);
+    assert_eq!(registry.names().len(), 6);
+
+    let elements: Vec<ScheduledElement> = ["B", "A", "C"].iter()
+        .map(|name| element(name))
+        .collect();
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(1);
+
+    let schedule = registry.get("Alphabetical").unwrap()
+        .schedule(&elements, &calendar, &Rules::default());
+
+    // "A" is first every day
+    assert!(schedule.assignments.iter().all(|a| a.element_id == 1));
+}
+
+#[test]
+fn first_scheduler_is_the_fallback() {
+    assert!(Registry::new().first().is_none());
+    assert_eq!(Registry::default().first().unwrap().name(), "Fast rotation");
+
+    let mut registry = Registry::new();
+    registry.register(Alphabetical);
+    assert_eq!(registry.first().unwrap().name(), "Alphabetical");
+}
+
+#[test]
+fn every_scheduler_keeps_hard_rules() {
+    let mut elements: Vec<ScheduledElement> = ["A", "B", "C", "D", "E", "F", "G", "H"]
+        .iter()
+        .map(|name| element(name))
+        .collect();
+    elements[0].avoid_days = vec![Day::Tuesday];
+    elements[1].max_assignments = Some(5);
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(3);
+
+    let registry = Registry::default();
+
+    for name in registry.names() {
+        let scheduler = registry.get(&name).unwrap();
+
+        for seed in 0..5 {
+            let rules = Rules {
+                seed: Some(seed),
+                max_consecutive_days: Some(5),
+                min_days_off: 2,
+                pairings: vec![
+                    Pairing::new("C", "D", PairRule::Together),
+                    Pairing::new("E", "F", PairRule::Apart),
+                ],
+                ..Rules::default()
+            };
+
+            let schedule = scheduler.schedule(&elements, &calendar, &rules);
+            let again = scheduler.schedule(&elements, &calendar, &rules);
+
+            assert_eq!(schedule.assignments, again.assignments, "{}", name);
+            assert!(schedule.by_element(0)
+                .all(|a| a.date.weekday().num_days_from_sunday() != 2));
+            assert!(schedule.by_element(1).count() <= 5, "{}", name);
+            assert_eq!(dates_of(&schedule, 2), dates_of(&schedule, 3), "{}", name);
+            assert!(
+                dates_of(&schedule, 4).is_disjoint(&dates_of(&schedule, 5)),
+                "{}", name,
+            );
+
+            for (date, assignments) in schedule.by_date() {
+                assert!(assignments.len() <= 3, "{} {}", name, date);
+            }
+
+            assert!(schedule.assignments.len() > 50, "{}", name);
+        }
+    }
+}
+
+#[test]
+fn round_robin_takes_everyone_in_turn() {
+    let elements: Vec<ScheduledElement> = ["A", "B", "C", "D", "E"].iter()
+        .map(|name| element(name))
+        .collect();
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(2);
+
+    let rules = Rules { seed: Some(1), ..Rules::default() };
+    let schedule = Registry::default().get("Round-robin").unwrap()
+        .schedule(&elements, &calendar, &rules);
+    let turns: Vec<usize> = schedule.assignments.iter()
+        .map(|a| a.element_id)
+        .collect();
+
+    // Nobody gets a second turn before everyone had one
+    for window in turns.windows(elements.len()) {
+        let unique: HashSet<&usize> = window.iter().collect();
+        assert_eq!(unique.len(), elements.len(), "{:?}", turns);
+    }
+}
@@ -0,0 +1,60 @@
+use chrono::Datelike;
+use schedulez::{
+    calendar::Calendar,
+    reader::ScheduledElement,
+    trace::{ Action, Reason },
+    types::{ Day, Month },
+    worker::{ self, Rules },
+};
+
+fn element(name: &str) -> ScheduledElement {
+    ScheduledElement { text: name.to_owned(), ..ScheduledElement::default() }
+}
+
+#[test]
+fn trace_explains_every_assignment() {
+    let mut elements: Vec<ScheduledElement> = ["A", "B", "C", "D", "E"].iter()
+        .map(|name| element(name))
+        .collect();
+    elements[0].avoid_days = vec![Day::Wednesday];
+    elements[1].prefer_days = vec![Day::Monday];
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(2);
+
+    let rules = Rules { seed: Some(8), ..Rules::default() };
+    let untraced = worker::process(&elements, &calendar, &rules);
+    assert!(untraced.trace.is_empty());
+
+    let rules = Rules { trace: true, ..rules };
+    let schedule = worker::process(&elements, &calendar, &rules);
+
+    // Tracing doesn't change the schedule
+    assert_eq!(schedule.assignments, untraced.assignments);
+
+    for assignment in schedule.assignments.iter() {
+        let last = schedule.trace.explain(assignment.element_id, assignment.date)
+            .last()
+            .unwrap();
+
+        assert_eq!(last.action, Action::Picked, "{:?}", assignment);
+        assert_eq!(last.name, schedule.element(assignment).text);
+    }
+
+    let skipped: Vec<_> = schedule.trace.entries.iter()
+        .filter(|entry| entry.element_id == 0 && entry.action == Action::Skipped)
+        .collect();
+
+    assert!(!skipped.is_empty());
+    assert!(skipped.iter().all(|entry| entry.reason == Reason::AvoidsDay
+        && entry.date.weekday().num_days_from_sunday() == 3));
+
+    let json: serde_json::Value = serde_json::from_str(
+        &schedule.trace.to_json()
+    ).unwrap();
+    let first = &json.as_array().unwrap()[0];
+
+    assert_eq!(first["date"], "2026-11-02");
+    assert!(first["action"].is_string());
+    assert!(first["debt"].is_number());
+}
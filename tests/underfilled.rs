@@ -0,0 +1,144 @@
+use chrono::{ Datelike, NaiveDate };
+use schedulez::{
+    calendar::{ Calendar, DateRange },
+    pairing::{ Pairing, PairRule },
+    reader::ScheduledElement,
+    schedule::{ Schedule, ScheduleWarning },
+    scheduler::{ Draft, Registry, Scheduler },
+    stats::ScheduleStats,
+    trace::Reason,
+    types::{ Day, Month },
+    worker::{ self, Rules },
+};
+
+fn element(name: &str) -> ScheduledElement {
+    ScheduledElement { text: name.to_owned(), ..ScheduledElement::default() }
+}
+
+fn elements() -> Vec<ScheduledElement> {
+    let mut elements: Vec<ScheduledElement> = ["A", "B", "C"].iter()
+        .map(|name| element(name))
+        .collect();
+    elements[0].avoid_days = vec![Day::Monday];
+    elements[1].leave = vec![DateRange::day(
+        NaiveDate::from_ymd_opt(2026, 11, 11).unwrap()
+    )];
+
+    elements
+}
+
+#[test]
+fn underfilled_days_are_reported_with_reasons() {
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(3);
+
+    let rules = Rules { seed: Some(2), ..Rules::default() };
+    let schedule = worker::process(&elements(), &calendar, &rules);
+
+    let gaps: Vec<_> = schedule.warnings.iter()
+        .filter_map(|warning| match warning {
+            ScheduleWarning::Underfilled {
+                date, shift, scheduled, headcount, reasons
+            } => Some((*date, shift.clone(), *scheduled, *headcount, reasons.clone())),
+            _ => None,
+        })
+        .collect();
+
+    // The five Mondays and the day of leave
+    assert_eq!(gaps.len(), 6);
+
+    for (date, shift, scheduled, headcount, reasons) in gaps.iter() {
+        assert_eq!(*shift, None);
+        assert_eq!((*scheduled, *headcount), (2, 3));
+
+        if date.weekday().num_days_from_sunday() == 1 {
+            assert_eq!(reasons, &vec![(Reason::AvoidsDay, 1)]);
+        } else {
+            assert_eq!(*date, NaiveDate::from_ymd_opt(2026, 11, 11).unwrap());
+            assert_eq!(reasons, &vec![(Reason::OnLeave, 1)]);
+        }
+    }
+
+    let text = schedule.warnings.iter()
+        .find(|warning| matches!(warning, ScheduleWarning::Underfilled { .. }))
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        text,
+        "Mon 2026-11-02 is short by 1, 2 of the 3 needed are scheduled \
+        (avoids the day: 1)",
+    );
+}
+
+#[test]
+fn every_scheduler_reports_its_gaps() {
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(3);
+
+    let rules = Rules { seed: Some(4), ..Rules::default() };
+    let registry = Registry::default();
+
+    for name in registry.names() {
+        let schedule = registry.get(&name).unwrap()
+            .schedule(&elements(), &calendar, &rules);
+
+        let gaps = schedule.warnings.iter()
+            .filter(|warning| matches!(warning, ScheduleWarning::Underfilled { .. }))
+            .count();
+
+        assert_eq!(gaps, ScheduleStats::new(&schedule).unmet_slots, "{}", name);
+        assert_eq!(gaps, 6, "{}", name);
+    }
+}
+
+/// Leaves every shift empty, so that each element's reason is reported.
+struct Nobody;
+
+impl Scheduler for Nobody {
+    fn name(&self) -> &str { "Nobody" }
+
+    fn schedule(
+        &self,
+        elements: &[ScheduledElement],
+        calendar: &Calendar,
+        rules: &Rules,
+    ) -> Schedule {
+        Draft::new(elements, calendar, rules).finish()
+    }
+}
+
+#[test]
+fn pairs_are_kept_apart_only_by_a_partner_who_cant_work() {
+    let mut elements: Vec<ScheduledElement> = ["A", "B", "C", "D"].iter()
+        .map(|name| element(name))
+        .collect();
+    elements[2].leave = vec![DateRange::new(
+        NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+        NaiveDate::from_ymd_opt(2026, 11, 30).unwrap(),
+    )];
+
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(4);
+
+    let rules = Rules {
+        pairings: vec![
+            Pairing::new("A", "B", PairRule::Together),
+            Pairing::new("C", "D", PairRule::Together),
+        ],
+        ..Rules::default()
+    };
+    let schedule = Nobody.schedule(&elements, &calendar, &rules);
+
+    let ScheduleWarning::Underfilled { reasons, .. } = schedule.warnings.iter()
+        .find(|warning| matches!(warning, ScheduleWarning::Underfilled { .. }))
+        .unwrap()
+    else {
+        unreachable!();
+    };
+
+    // A and B are merely unpicked, D can't work without C
+    assert_eq!(
+        reasons,
+        &vec![(Reason::Unpicked, 2), (Reason::OnLeave, 1), (Reason::KeptApart, 1)],
+    );
+}
@@ -0,0 +1,90 @@
+use schedulez::{
+    calendar::Calendar,
+    reader::{ Reader, ScheduledElement },
+    types::{ Day, Month },
+    validator::{ self, Severity },
+};
+
+fn reader(names: &[&str]) -> Reader {
+    let mut reader = Reader::default();
+    reader.elements = names.iter()
+        .enumerate()
+        .map(|(i, name)| ScheduledElement {
+            text: name.to_string(),
+            row: i + 2,
+            ..ScheduledElement::default()
+        })
+        .collect();
+
+    reader
+}
+
+fn calendar(headcount: usize) -> Calendar {
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(headcount);
+
+    calendar
+}
+
+/// The issues as `(severity, row, message)`.
+fn issues(reader: &Reader, calendar: &Calendar) -> Vec<(Severity, Option<usize>, String)> {
+    validator::validate(reader, calendar).issues.into_iter()
+        .map(|issue| (issue.severity, issue.row, issue.message))
+        .collect()
+}
+
+#[test]
+fn valid_roster_has_no_issues() {
+    let report = validator::validate(&reader(&["A", "B", "C"]), &calendar(2));
+
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+    assert!(!report.has_errors());
+}
+
+#[test]
+fn duplicate_and_blank_names_are_errors() {
+    let issues = issues(&reader(&["A", "B", "A", " "]), &calendar(1));
+
+    assert_eq!(issues, vec![
+        (Severity::Error, Some(4), "\"A\" is already on row 2".to_owned()),
+        (Severity::Error, Some(5), "The name is blank".to_owned()),
+    ]);
+}
+
+#[test]
+fn avoiding_every_working_day_is_a_warning() {
+    let mut calendar = calendar(1);
+    calendar.set_working_day(Day::Friday, false);
+
+    let mut reader = reader(&["A", "B"]);
+    reader.elements[0].avoid_days = vec![
+        Day::Monday, Day::Tuesday, Day::Wednesday, Day::Thursday,
+    ];
+
+    let report = validator::validate(&reader, &calendar);
+
+    assert!(!report.has_errors());
+    assert_eq!(
+        report.issues[0].to_string(),
+        "Warning (row 2): \"A\" avoids every working day",
+    );
+}
+
+#[test]
+fn roster_smaller_than_the_headcount_is_a_warning() {
+    let issues = issues(&reader(&["A", "B"]), &calendar(3));
+
+    assert_eq!(issues, vec![(
+        Severity::Warning,
+        None,
+        "There are 2 elements, but some days need 3".to_owned(),
+    )]);
+}
+
+#[test]
+fn empty_roster_is_an_error() {
+    let report = validator::validate(&Reader::default(), &calendar(1));
+
+    assert!(report.has_errors());
+    assert_eq!(report.issues[0].message, "No elements found in the file");
+}
@@ -0,0 +1,104 @@
+use std::{ fs, io::Read };
+
+use chrono::Datelike;
+use schedulez::{
+    calendar::Calendar,
+    reader::ScheduledElement,
+    types::{ Day, Month },
+    worker::{ self, Rules },
+    writer::Writer,
+};
+
+/// A Sunday to Thursday week, with Friday and Saturday off.
+fn calendar() -> Calendar {
+    let mut calendar = Calendar::new(2026, Month::November);
+    calendar.set_all_headcounts(1);
+    calendar.set_working_day(Day::Sunday, true);
+    calendar.set_working_day(Day::Friday, false);
+
+    calendar
+}
+
+/// Gets the style index of each cell of the first sheet of the workbook, as
+/// `(reference, style)`, e.g. `("B2", 1)`.
+fn cell_styles(path: &std::path::Path) -> Vec<(String, usize)> {
+    let mut archive = zip::ZipArchive::new(fs::File::open(path).unwrap()).unwrap();
+    let mut sheet = String::new();
+    archive.by_name("xl/worksheets/sheet1.xml").unwrap()
+        .read_to_string(&mut sheet)
+        .unwrap();
+
+    sheet.split("<c r=\"")
+        .skip(1)
+        .map(|cell| {
+            let reference = cell.split('"').next().unwrap().to_owned();
+            let style = cell.split_once(" s=\"")
+                .filter(|(attributes, _)| !attributes.contains('>'))
+                .map(|(_, rest)| rest.split('"').next().unwrap().parse().unwrap())
+                .unwrap_or_default();
+
+            (reference, style)
+        })
+        .collect()
+}
+
+#[test]
+fn days_off_are_not_scheduled() {
+    let elements: Vec<ScheduledElement> = ["A", "B", "C"].iter()
+        .map(|name| ScheduledElement {
+            text: name.to_string(),
+            ..ScheduledElement::default()
+        })
+        .collect();
+
+    let rules = Rules { seed: Some(6), ..Rules::default() };
+    let schedule = worker::process(&elements, &calendar(), &rules);
+
+    for (date, assignments) in schedule.by_date() {
+        let expected = match Day::from_u32(date.weekday().num_days_from_sunday()) {
+            Day::Friday | Day::Saturday => 0,
+            _ => 1,
+        };
+
+        assert_eq!(assignments.len(), expected, "{}", date);
+    }
+
+    // 22 of the days of November 2026 are from Sunday to Thursday
+    assert_eq!(schedule.assignments.len(), 22);
+}
+
+#[test]
+fn days_off_are_shaded_in_the_export() {
+    let elements = vec![ScheduledElement {
+        text: "A".to_owned(),
+        ..ScheduledElement::default()
+    }];
+    let schedule = worker::process(&elements, &calendar(), &Rules::default());
+
+    let mut writer = Writer::default();
+    writer.init(&schedule);
+
+    let path = std::env::temp_dir()
+        .join(format!("schedulez-{}-working-days.xlsx", std::process::id()));
+    writer.save_xlsx(&path).unwrap();
+    let styles = cell_styles(&path);
+    fs::remove_file(&path).unwrap();
+
+    let style = |reference: &str| styles.iter()
+        .find(|(other, _)| other == reference)
+        .map(|(_, style)| *style)
+        .unwrap();
+
+    // The names of the days on row 2 and the dates of the first week, which
+    // starts on Sunday, on row 3. Each day is three columns wide from "B".
+    for row in [2, 3] {
+        let sunday = style(&format!("B{}", row));
+        let thursday = style(&format!("N{}", row));
+        let friday = style(&format!("Q{}", row));
+        let saturday = style(&format!("T{}", row));
+
+        assert_eq!(sunday, thursday);
+        assert_eq!(friday, saturday);
+        assert_ne!(thursday, friday);
+    }
+}